@@ -0,0 +1,697 @@
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{Data, DataEnum, DeriveInput, Fields, LitStr, Member};
+
+const AST: &str = "::impl_iter_tuple_kind_of::v4::ast";
+const COMPONENTS: &str = "::impl_iter_tuple_kind_of::v4::components";
+const CONTENT: &str = "::impl_iter_tuple_kind_of::v4::content";
+const CONTEXT: &str = "::impl_iter_tuple_kind_of::v4::context";
+const STYLE: &str = "::impl_iter_tuple_kind_of::v4::style";
+
+const VOID: &[&str] = &["meta", "link", "img", "br", "hr", "input"];
+
+enum Segment {
+    Literal(String),
+    Field(String),
+}
+
+fn parse_template(template: &LitStr) -> syn::Result<Vec<Segment>> {
+    let source = template.value();
+    let mut segments = Vec::new();
+    let mut literal = String::new();
+    let mut chars = source.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                literal.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                literal.push('}');
+            }
+            '{' => {
+                let mut field = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => field.push(c),
+                        None => {
+                            return Err(syn::Error::new(
+                                template.span(),
+                                "unclosed `{` in template",
+                            ))
+                        }
+                    }
+                }
+                if !literal.is_empty() {
+                    segments.push(Segment::Literal(std::mem::take(&mut literal)));
+                }
+                segments.push(Segment::Field(field.trim().to_string()));
+            }
+            '}' => {
+                return Err(syn::Error::new(
+                    template.span(),
+                    "unmatched `}` in template, use `}}` for a literal brace",
+                ))
+            }
+            c => literal.push(c),
+        }
+    }
+
+    if !literal.is_empty() {
+        segments.push(Segment::Literal(literal));
+    }
+
+    Ok(segments)
+}
+
+enum Part {
+    Char(char),
+    Field(String),
+}
+
+enum Token {
+    Text(String),
+    Field(String),
+    Start {
+        name: String,
+        attributes: Vec<(String, Vec<Segment>)>,
+        close: bool,
+    },
+    End(String),
+}
+
+struct Tokenizer<'a> {
+    template: &'a LitStr,
+    parts: Vec<Part>,
+    index: usize,
+}
+
+impl Tokenizer<'_> {
+    fn error(&self, message: String) -> syn::Error {
+        syn::Error::new(self.template.span(), message)
+    }
+
+    fn peek_char(&self) -> Option<char> {
+        match self.parts.get(self.index) {
+            Some(Part::Char(c)) => Some(*c),
+            _ => None,
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek_char().is_some_and(char::is_whitespace) {
+            self.index += 1;
+        }
+    }
+
+    fn expect(&mut self, expected: char, name: &str) -> syn::Result<()> {
+        if self.peek_char() != Some(expected) {
+            return Err(self.error(format!("expected `{expected}` in `<{name}>` tag")));
+        }
+        self.index += 1;
+        Ok(())
+    }
+
+    fn name(&mut self) -> String {
+        let mut name = String::new();
+        while let Some(c) = self
+            .peek_char()
+            .filter(|c| c.is_alphanumeric() || matches!(c, '-' | '_' | ':'))
+        {
+            name.push(c);
+            self.index += 1;
+        }
+        name
+    }
+
+    fn value(&mut self, name: &str) -> syn::Result<Vec<Segment>> {
+        let quote = self.peek_char().filter(|c| matches!(c, '"' | '\''));
+        if quote.is_some() {
+            self.index += 1;
+        }
+
+        let mut value = Vec::new();
+        let mut literal = String::new();
+
+        loop {
+            match self.parts.get(self.index) {
+                None if quote.is_some() => {
+                    return Err(self.error(format!("unclosed attribute value in `<{name}>` tag")))
+                }
+                None => break,
+                Some(Part::Char(c)) if Some(*c) == quote => {
+                    self.index += 1;
+                    break;
+                }
+                Some(Part::Char(c)) if quote.is_none() && (c.is_whitespace() || *c == '>') => break,
+                Some(Part::Char(c)) => literal.push(*c),
+                Some(Part::Field(field)) => {
+                    if !literal.is_empty() {
+                        value.push(Segment::Literal(decode(&std::mem::take(&mut literal))));
+                    }
+                    value.push(Segment::Field(field.clone()));
+                }
+            }
+            self.index += 1;
+        }
+
+        if !literal.is_empty() {
+            value.push(Segment::Literal(decode(&literal)));
+        }
+
+        Ok(value)
+    }
+
+    fn tag(&mut self) -> syn::Result<Token> {
+        self.index += 1;
+
+        if self.peek_char() == Some('/') {
+            self.index += 1;
+            let name = self.name().to_ascii_lowercase();
+            self.skip_whitespace();
+            self.expect('>', &name)?;
+            return Ok(Token::End(name));
+        }
+
+        let name = self.name().to_ascii_lowercase();
+        let mut attributes = Vec::new();
+
+        loop {
+            self.skip_whitespace();
+            match self.parts.get(self.index) {
+                Some(Part::Char('>')) => {
+                    self.index += 1;
+                    let close = VOID.contains(&name.as_str());
+                    return Ok(Token::Start {
+                        name,
+                        attributes,
+                        close,
+                    });
+                }
+                Some(Part::Char('/')) => {
+                    self.index += 1;
+                    self.expect('>', &name)?;
+                    return Ok(Token::Start {
+                        name,
+                        attributes,
+                        close: true,
+                    });
+                }
+                Some(Part::Char(c)) => {
+                    let c = *c;
+                    let attribute = self.name();
+                    if attribute.is_empty() {
+                        return Err(self.error(format!("unexpected `{c}` in `<{name}>` tag")));
+                    }
+                    self.skip_whitespace();
+                    let value = if self.peek_char() == Some('=') {
+                        self.index += 1;
+                        self.skip_whitespace();
+                        self.value(&name)?
+                    } else {
+                        Vec::new()
+                    };
+                    attributes.push((attribute.to_ascii_lowercase(), value));
+                }
+                Some(Part::Field(field)) => {
+                    return Err(self.error(format!(
+                        "`{{{field}}}` can only be used in text or attribute values, not in the `<{name}>` tag itself"
+                    )))
+                }
+                None => return Err(self.error(format!("unclosed `<{name}>` tag in template"))),
+            }
+        }
+    }
+
+    fn tokens(mut self) -> syn::Result<Vec<Token>> {
+        let mut tokens = Vec::new();
+        let mut text = String::new();
+
+        while let Some(part) = self.parts.get(self.index) {
+            let tag = matches!(part, Part::Char('<'))
+                && matches!(
+                    self.parts.get(self.index + 1),
+                    Some(Part::Char(c)) if c.is_ascii_alphabetic() || *c == '/'
+                );
+
+            match part {
+                _ if tag => {
+                    if !text.is_empty() {
+                        tokens.push(Token::Text(std::mem::take(&mut text)));
+                    }
+                    tokens.push(self.tag()?);
+                }
+                Part::Field(field) => {
+                    if !text.is_empty() {
+                        tokens.push(Token::Text(std::mem::take(&mut text)));
+                    }
+                    tokens.push(Token::Field(field.clone()));
+                    self.index += 1;
+                }
+                Part::Char(c) => {
+                    text.push(*c);
+                    self.index += 1;
+                }
+            }
+        }
+
+        if !text.is_empty() {
+            tokens.push(Token::Text(text));
+        }
+
+        Ok(tokens)
+    }
+}
+
+fn decode(value: &str) -> String {
+    value
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&")
+}
+
+fn tokenize(template: &LitStr) -> syn::Result<Vec<Token>> {
+    let mut parts = Vec::new();
+
+    for segment in parse_template(template)? {
+        match segment {
+            Segment::Literal(literal) => parts.extend(literal.chars().map(Part::Char)),
+            Segment::Field(field) => parts.push(Part::Field(field)),
+        }
+    }
+
+    Tokenizer {
+        template,
+        parts,
+        index: 0,
+    }
+    .tokens()
+}
+
+struct Field {
+    member: Member,
+    list: bool,
+}
+
+fn fields(fields: &Fields) -> syn::Result<Vec<Field>> {
+    let mut result = Vec::new();
+
+    for (index, field) in fields.iter().enumerate() {
+        let member = match &field.ident {
+            Some(ident) => Member::Named(ident.clone()),
+            None => Member::Unnamed(index.into()),
+        };
+        let mut list = false;
+
+        for attr in &field.attrs {
+            if !attr.path().is_ident("component") {
+                continue;
+            }
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("list") {
+                    list = true;
+                    Ok(())
+                } else if meta.path.is_ident("skip") {
+                    // `skip` belongs to `#[derive(ComponentList)]`
+                    Ok(())
+                } else {
+                    Err(meta.error("unknown field attribute, expected `list` or `skip`"))
+                }
+            })?;
+        }
+
+        result.push(Field { member, list });
+    }
+
+    Ok(result)
+}
+
+fn member_name(member: &Member) -> String {
+    match member {
+        Member::Named(ident) => ident.to_string(),
+        Member::Unnamed(index) => index.index.to_string(),
+    }
+}
+
+#[derive(Default)]
+struct Attributes {
+    template: Option<LitStr>,
+    style: Option<LitStr>,
+    phrasing: bool,
+}
+
+fn attributes(input: &DeriveInput) -> syn::Result<Attributes> {
+    let mut attributes = Attributes::default();
+
+    for attr in &input.attrs {
+        if !attr.path().is_ident("component") {
+            continue;
+        }
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("template") {
+                attributes.template = Some(meta.value()?.parse()?);
+                Ok(())
+            } else if meta.path.is_ident("style") {
+                attributes.style = Some(meta.value()?.parse()?);
+                Ok(())
+            } else if meta.path.is_ident("phrasing") {
+                attributes.phrasing = true;
+                Ok(())
+            } else {
+                Err(meta.error(
+                    "unknown attribute, expected `template = \"...\"`, `style = \"...\"` or `phrasing`",
+                ))
+            }
+        })?;
+    }
+
+    Ok(attributes)
+}
+
+struct Generator<'a> {
+    components: syn::Path,
+    context: syn::Path,
+    component: String,
+    template: &'a LitStr,
+    fields: Vec<Field>,
+    scoped: bool,
+    children: Vec<TokenStream>,
+}
+
+impl Generator<'_> {
+    fn error(&self, message: String) -> syn::Error {
+        syn::Error::new(self.template.span(), message)
+    }
+
+    fn is_scope(&self, name: &str) -> bool {
+        self.scoped
+            && name == "scope"
+            && !self
+                .fields
+                .iter()
+                .any(|field| member_name(&field.member) == name)
+    }
+
+    fn field(&self, name: &str) -> syn::Result<&Field> {
+        self.fields
+            .iter()
+            .find(|field| member_name(&field.member) == name)
+            .ok_or_else(|| self.error(format!("unknown field `{name}` in template")))
+    }
+
+    fn attribute(&self, attribute: &str, value: Vec<Segment>) -> syn::Result<TokenStream> {
+        let mut format = String::new();
+        let mut args = Vec::new();
+
+        for segment in value {
+            match segment {
+                Segment::Literal(literal) => {
+                    format.push_str(&literal.replace('{', "{{").replace('}', "}}"))
+                }
+                Segment::Field(name) if self.is_scope(&name) => {
+                    format.push_str("{}");
+                    args.push(quote!(scope));
+                }
+                Segment::Field(name) => {
+                    let field = self.field(&name)?;
+                    if field.list {
+                        return Err(self.error(format!(
+                            "list field `{name}` cannot be used in an attribute"
+                        )));
+                    }
+                    let member = &field.member;
+                    format.push_str("{}");
+                    args.push(quote!(self.#member));
+                }
+            }
+        }
+
+        Ok(if args.is_empty() {
+            quote!(cx.attribute(#attribute, #format);)
+        } else {
+            quote!(cx.attribute(#attribute, &::std::format!(#format, #(#args),*));)
+        })
+    }
+
+    fn statements(
+        &mut self,
+        tokens: &mut std::vec::IntoIter<Token>,
+        open: Option<&str>,
+    ) -> syn::Result<Vec<TokenStream>> {
+        let components = &self.components.clone();
+        let context = &self.context.clone();
+        let mut statements = Vec::new();
+
+        while let Some(token) = tokens.next() {
+            match token {
+                Token::Text(text) => {
+                    statements.push(quote!(cx.raw(&#components::TrustedHtml::from_static(#text));));
+                }
+                Token::Field(name) if self.is_scope(&name) => {
+                    statements.push(quote!(cx.text(&scope);));
+                }
+                Token::Field(name) => {
+                    let field = self.field(&name)?;
+                    let member = field.member.clone();
+                    if field.list {
+                        statements.push(
+                            quote!(#components::ComponentList::render_components_to(&self.#member, cx);),
+                        );
+                        self.children.push(
+                            quote!(#components::ComponentList::child_components(&self.#member)),
+                        );
+                    } else {
+                        statements
+                            .push(quote!(#components::Component::render_to(&self.#member, cx);));
+                        self.children.push(quote!(
+                            ::std::vec![&self.#member as &dyn #components::Component]
+                        ));
+                    }
+                }
+                Token::Start {
+                    name,
+                    attributes,
+                    close,
+                } => {
+                    let component = &self.component;
+                    let level = match name.as_str() {
+                        "h1" => Some(1u8),
+                        "h2" => Some(2),
+                        "h3" => Some(3),
+                        _ => None,
+                    };
+                    if level.is_some() && attributes.iter().any(|(attribute, _)| attribute == "id")
+                    {
+                        return Err(self.error(format!(
+                            "`<{name}>` gets its `id` from its text, remove the `id` attribute"
+                        )));
+                    }
+
+                    statements.push(quote!(cx.enter(#component, #name);));
+                    for (attribute, value) in attributes {
+                        statements.push(self.attribute(&attribute, value)?);
+                    }
+
+                    if !close {
+                        let content = self.statements(tokens, Some(&name))?;
+                        statements.push(match level {
+                            Some(level) => quote! {
+                                {
+                                    let content = |cx: &mut #context::RenderContext<'_>| {
+                                        #(#content)*
+                                    };
+                                    cx.anchor(#level, &content);
+                                    content(cx);
+                                }
+                            },
+                            None => quote!(#(#content)*),
+                        });
+                    }
+                    statements.push(quote!(cx.exit(#name);));
+                }
+                Token::End(name) if open == Some(name.as_str()) => return Ok(statements),
+                Token::End(name) => {
+                    return Err(match open {
+                        Some(open) => self.error(format!(
+                            "expected `</{open}>` in template, found `</{name}>`"
+                        )),
+                        None => self.error(format!("unexpected `</{name}>` in template")),
+                    })
+                }
+            }
+        }
+
+        match open {
+            Some(open) => Err(self.error(format!("unclosed `<{open}>` in template"))),
+            None => Ok(statements),
+        }
+    }
+}
+
+fn render_struct(
+    input: &DeriveInput,
+    attributes: &Attributes,
+    struct_fields: &Fields,
+) -> syn::Result<TokenStream> {
+    let components: syn::Path = syn::parse_str(COMPONENTS).unwrap();
+    let context: syn::Path = syn::parse_str(CONTEXT).unwrap();
+    let template = attributes.template.as_ref().ok_or_else(|| {
+        syn::Error::new(
+            input.ident.span(),
+            "missing `#[component(template = \"...\")]` attribute",
+        )
+    })?;
+    let mut statements = Vec::new();
+
+    if let Some(style) = &attributes.style {
+        let style_module: syn::Path = syn::parse_str(STYLE).unwrap();
+        statements.push(quote! {
+            let scope = #style_module::scoped_style::<Self>(cx, #style);
+        });
+    }
+
+    let mut generator = Generator {
+        components: components.clone(),
+        context: context.clone(),
+        component: input.ident.to_string(),
+        template,
+        fields: fields(struct_fields)?,
+        scoped: attributes.style.is_some(),
+        children: Vec::new(),
+    };
+    statements.extend(generator.statements(&mut tokenize(template)?.into_iter(), None)?);
+    let children = generator.children;
+
+    let children = (!children.is_empty()).then(|| {
+        quote! {
+            fn children(&self) -> ::std::vec::Vec<&dyn #components::Component> {
+                [#(#children),*].concat()
+            }
+        }
+    });
+
+    Ok(quote! {
+        fn render_to(&self, cx: &mut #context::RenderContext) {
+            #(#statements)*
+        }
+
+        #children
+    })
+}
+
+fn render_enum(attributes: &Attributes, data: &DataEnum) -> syn::Result<TokenStream> {
+    let ast: syn::Path = syn::parse_str(AST).unwrap();
+    let components: syn::Path = syn::parse_str(COMPONENTS).unwrap();
+    let context: syn::Path = syn::parse_str(CONTEXT).unwrap();
+
+    if let Some(attr) = attributes.template.as_ref().or(attributes.style.as_ref()) {
+        return Err(syn::Error::new_spanned(
+            attr,
+            "templates are not supported on enums, each variant renders its own component",
+        ));
+    }
+
+    let mut render_arms = Vec::new();
+    let mut export_arms = Vec::new();
+    let mut tag_arms = Vec::new();
+    let mut children_arms = Vec::new();
+
+    for variant in &data.variants {
+        let ident = &variant.ident;
+        let (pattern, inner) = match &variant.fields {
+            Fields::Unit => {
+                render_arms.push(quote!(Self::#ident => {}));
+                export_arms.push(quote!(Self::#ident => ::std::result::Result::Ok(()),));
+                tag_arms.push(quote!(Self::#ident => ::std::option::Option::None,));
+                children_arms.push(quote!(Self::#ident => ::std::vec::Vec::new(),));
+                continue;
+            }
+            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+                (quote!(Self::#ident(inner)), quote!(inner))
+            }
+            Fields::Named(fields) if fields.named.len() == 1 => {
+                let field = fields.named[0].ident.as_ref().unwrap();
+                (quote!(Self::#ident { #field }), quote!(#field))
+            }
+            fields => {
+                return Err(syn::Error::new_spanned(
+                    fields,
+                    "each variant must hold exactly one component",
+                ))
+            }
+        };
+        render_arms.push(quote!(#pattern => #components::Component::render_to(#inner, cx),));
+        export_arms.push(quote!(#pattern => #components::Component::export(#inner, nodes),));
+        tag_arms.push(quote!(#pattern => #components::Component::tag(#inner),));
+        children_arms.push(quote!(#pattern => #components::Component::children(#inner),));
+    }
+
+    Ok(quote! {
+        fn render_to(&self, cx: &mut #context::RenderContext) {
+            match self {
+                #(#render_arms)*
+            }
+        }
+
+        fn export(
+            &self,
+            nodes: &mut ::std::vec::Vec<#ast::Node>,
+        ) -> ::std::result::Result<(), #ast::ExportError> {
+            match self {
+                #(#export_arms)*
+            }
+        }
+
+        fn tag(&self) -> ::std::option::Option<&'static str> {
+            match self {
+                #(#tag_arms)*
+            }
+        }
+
+        fn children(&self) -> ::std::vec::Vec<&dyn #components::Component> {
+            match self {
+                #(#children_arms)*
+            }
+        }
+    })
+}
+
+pub fn derive(input: DeriveInput) -> syn::Result<TokenStream> {
+    let components: syn::Path = syn::parse_str(COMPONENTS).unwrap();
+    let content: syn::Path = syn::parse_str(CONTENT).unwrap();
+    let attributes = attributes(&input)?;
+
+    let methods = match &input.data {
+        Data::Struct(data) => render_struct(&input, &attributes, &data.fields)?,
+        Data::Enum(data) => render_enum(&attributes, data)?,
+        Data::Union(_) => {
+            return Err(syn::Error::new(
+                Span::call_site(),
+                "`Component` can only be derived for structs and enums",
+            ))
+        }
+    };
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let phrasing = attributes.phrasing.then(|| {
+        quote! {
+            impl #impl_generics #content::Phrasing for #name #ty_generics #where_clause {}
+        }
+    });
+
+    Ok(quote! {
+        impl #impl_generics #components::Component for #name #ty_generics #where_clause {
+            #methods
+        }
+
+        impl #impl_generics #content::Flow for #name #ty_generics #where_clause {}
+
+        #phrasing
+    })
+}
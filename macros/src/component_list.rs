@@ -0,0 +1,84 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{parse_quote, Data, DeriveInput, Fields};
+
+const COMPONENTS: &str = "::impl_iter_tuple_kind_of::v4::components";
+
+fn skipped(field: &syn::Field) -> syn::Result<bool> {
+    let mut skip = false;
+
+    for attr in &field.attrs {
+        if !attr.path().is_ident("component") {
+            continue;
+        }
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("skip") {
+                skip = true;
+                Ok(())
+            } else if meta.path.is_ident("list") {
+                // `list` belongs to `#[derive(Component)]`
+                Ok(())
+            } else {
+                Err(meta.error("unknown field attribute, expected `skip` or `list`"))
+            }
+        })?;
+    }
+
+    Ok(skip)
+}
+
+pub fn derive(mut input: DeriveInput) -> syn::Result<TokenStream> {
+    let components: syn::Path = syn::parse_str(COMPONENTS).unwrap();
+
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(syn::Error::new(
+                    input.ident.span(),
+                    "`ComponentList` can only be derived for structs with named fields, use a tuple instead",
+                ))
+            }
+        },
+        _ => {
+            return Err(syn::Error::new(
+                input.ident.span(),
+                "`ComponentList` can only be derived for structs",
+            ))
+        }
+    };
+
+    let mut members = Vec::new();
+    let mut types = Vec::new();
+
+    for field in fields {
+        if skipped(field)? {
+            continue;
+        }
+        members.push(field.ident.clone().unwrap());
+        types.push(field.ty.clone());
+    }
+
+    let len = members.len();
+    let where_clause = input.generics.make_where_clause();
+    for ty in &types {
+        where_clause
+            .predicates
+            .push(parse_quote!(#ty: #components::Component + 'static));
+    }
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics #components::ComponentList for #name #ty_generics #where_clause {
+            type Item = dyn #components::Component;
+
+            type IntoIter<'a> = ::core::array::IntoIter<&'a (dyn #components::Component + 'static), #len> where Self: 'a;
+
+            fn iter_components<'a>(&'a self) -> Self::IntoIter<'a> {
+                [#(&self.#members as &dyn #components::Component),*].into_iter()
+            }
+        }
+    })
+}
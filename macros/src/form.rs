@@ -0,0 +1,207 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{meta::ParseNestedMeta, Data, DeriveInput, Fields, LitStr, Token};
+
+const FORMS: &str = "::impl_iter_tuple_kind_of::v4::forms";
+
+fn ignore(meta: ParseNestedMeta) -> syn::Result<()> {
+    if meta.input.peek(Token![=]) {
+        meta.value()?.parse::<syn::Expr>()?;
+    } else if meta.input.peek(syn::token::Paren) {
+        meta.parse_nested_meta(ignore)?;
+    }
+    Ok(())
+}
+
+fn rename_all(input: &DeriveInput) -> syn::Result<Option<LitStr>> {
+    let mut rule = None;
+
+    for attr in &input.attrs {
+        if !attr.path().is_ident("serde") {
+            continue;
+        }
+        attr.parse_nested_meta(|meta| {
+            if !meta.path.is_ident("rename_all") {
+                return ignore(meta);
+            }
+            if meta.input.peek(Token![=]) {
+                rule = Some(meta.value()?.parse()?);
+                return Ok(());
+            }
+            meta.parse_nested_meta(|meta| {
+                if meta.path.is_ident("deserialize") {
+                    rule = Some(meta.value()?.parse()?);
+                    Ok(())
+                } else {
+                    ignore(meta)
+                }
+            })
+        })?;
+    }
+
+    Ok(rule)
+}
+
+fn apply(rule: &LitStr, name: &str) -> syn::Result<String> {
+    let words = name.split('_');
+    let pascal = || {
+        words
+            .clone()
+            .map(|word| {
+                let mut chars = word.chars();
+                chars
+                    .next()
+                    .map(|first| first.to_uppercase().chain(chars).collect::<String>())
+                    .unwrap_or_default()
+            })
+            .collect::<String>()
+    };
+
+    Ok(match rule.value().as_str() {
+        "lowercase" | "snake_case" => name.to_string(),
+        "UPPERCASE" | "SCREAMING_SNAKE_CASE" => name.to_uppercase(),
+        "kebab-case" => name.replace('_', "-"),
+        "SCREAMING-KEBAB-CASE" => name.replace('_', "-").to_uppercase(),
+        "PascalCase" => pascal(),
+        "camelCase" => {
+            let pascal = pascal();
+            let mut chars = pascal.chars();
+            chars
+                .next()
+                .map(|first| first.to_lowercase().chain(chars).collect::<String>())
+                .unwrap_or_default()
+        }
+        _ => return Err(syn::Error::new_spanned(rule, "unknown `rename_all` rule")),
+    })
+}
+
+struct Field {
+    member: syn::Ident,
+    variant: syn::Ident,
+    name: String,
+}
+
+fn field(field: &syn::Field, rule: Option<&LitStr>) -> syn::Result<Option<Field>> {
+    let member = field.ident.clone().unwrap();
+    let ident = member.to_string();
+    let ident = ident.trim_start_matches("r#");
+    let mut name = None;
+    let mut skip = false;
+
+    for attr in &field.attrs {
+        if !attr.path().is_ident("serde") {
+            continue;
+        }
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("skip") || meta.path.is_ident("skip_deserializing") {
+                skip = true;
+                Ok(())
+            } else if meta.path.is_ident("flatten") {
+                Err(meta.error("flattened fields cannot be rendered as form controls"))
+            } else if meta.path.is_ident("rename") {
+                if meta.input.peek(Token![=]) {
+                    name = Some(meta.value()?.parse::<LitStr>()?.value());
+                    return Ok(());
+                }
+                meta.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("deserialize") {
+                        name = Some(meta.value()?.parse::<LitStr>()?.value());
+                        Ok(())
+                    } else {
+                        ignore(meta)
+                    }
+                })
+            } else {
+                ignore(meta)
+            }
+        })?;
+    }
+
+    if skip {
+        return Ok(None);
+    }
+
+    let name = match (name, rule) {
+        (Some(name), _) => name,
+        (None, Some(rule)) => apply(rule, ident)?,
+        (None, None) => ident.to_string(),
+    };
+    let variant = format_ident!(
+        "{}",
+        apply(&LitStr::new("PascalCase", member.span()), ident)?,
+        span = member.span()
+    );
+
+    Ok(Some(Field {
+        member,
+        variant,
+        name,
+    }))
+}
+
+pub fn derive(input: DeriveInput) -> syn::Result<TokenStream> {
+    let forms: syn::Path = syn::parse_str(FORMS).unwrap();
+
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(syn::Error::new(
+                    input.ident.span(),
+                    "`FormData` can only be derived for structs with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(syn::Error::new(
+                input.ident.span(),
+                "`FormData` can only be derived for structs",
+            ))
+        }
+    };
+
+    let rule = rename_all(&input)?;
+    let fields = fields
+        .iter()
+        .filter_map(|f| field(f, rule.as_ref()).transpose())
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    let vis = &input.vis;
+    let ident = &input.ident;
+    let enum_ident = format_ident!("{}Field", ident);
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let variants = fields
+        .iter()
+        .map(|field| &field.variant)
+        .collect::<Vec<_>>();
+    let members = fields.iter().map(|field| &field.member);
+    let names = fields.iter().map(|field| &field.name);
+
+    Ok(quote! {
+        #[derive(Clone, Copy, PartialEq, Debug)]
+        #vis enum #enum_ident {
+            #(#variants,)*
+        }
+
+        impl #forms::FormField for #enum_ident {
+            const ALL: &'static [Self] = &[#(Self::#variants,)*];
+
+            fn name(self) -> &'static str {
+                match self {
+                    #(Self::#variants => #names,)*
+                }
+            }
+        }
+
+        impl #impl_generics #forms::FormData for #ident #ty_generics #where_clause {
+            type Field = #enum_ident;
+
+            fn value(&self, field: Self::Field) -> ::std::string::String {
+                match field {
+                    #(#enum_ident::#variants => ::std::string::ToString::to_string(&self.#members),)*
+                }
+            }
+        }
+    })
+}
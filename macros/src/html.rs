@@ -0,0 +1,339 @@
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote, quote_spanned};
+use syn::{
+    braced,
+    parse::{Parse, ParseStream},
+    Expr, Ident, LitStr, Path, Token,
+};
+
+const CONTAINERS: &[&str] = &[
+    "h1", "h2", "h3", "p", "strong", "em", "code", "span", "small", "mark", "header", "nav",
+    "main", "section", "article", "aside", "footer",
+];
+
+const MAX_TUPLE_LEN: usize = 12;
+
+struct Input {
+    components: Path,
+    nodes: Vec<Node>,
+}
+
+impl Parse for Input {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let components = input.parse()?;
+        input.parse::<Token![;]>()?;
+
+        let mut nodes = Vec::new();
+        while !input.is_empty() {
+            if input.peek(Token![<]) && input.peek2(Token![/]) {
+                input.parse::<Token![<]>()?;
+                input.parse::<Token![/]>()?;
+                let name: Ident = input.parse()?;
+                return Err(syn::Error::new(
+                    name.span(),
+                    format!("unexpected closing tag `</{name}>`"),
+                ));
+            }
+            nodes.push(input.parse()?);
+        }
+
+        Ok(Self { components, nodes })
+    }
+}
+
+enum Node {
+    Element(Element),
+    Text(LitStr),
+    Expr(Expr),
+}
+
+impl Parse for Node {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.peek(LitStr) {
+            return Ok(Node::Text(input.parse()?));
+        }
+
+        if input.peek(syn::token::Brace) {
+            let content;
+            braced!(content in input);
+            return Ok(Node::Expr(content.parse()?));
+        }
+
+        if input.peek(Token![<]) {
+            return Ok(Node::Element(input.parse()?));
+        }
+
+        Err(input.error("expected an element, a string literal or a `{ ... }` expression"))
+    }
+}
+
+struct Attribute {
+    name: Ident,
+    value: Expr,
+}
+
+struct Element {
+    name: Ident,
+    attributes: Vec<Attribute>,
+    children: Vec<Node>,
+}
+
+fn is_void(name: &Ident) -> bool {
+    name == "img" || name == "br"
+}
+
+impl Parse for Element {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        input.parse::<Token![<]>()?;
+        let name: Ident = input.parse()?;
+
+        let mut attributes = Vec::new();
+        while !(input.peek(Token![>]) || input.peek(Token![/]) || input.is_empty()) {
+            let attribute_name: Ident = input.parse()?;
+            input.parse::<Token![=]>()?;
+            let value = if input.peek(syn::token::Brace) {
+                let content;
+                braced!(content in input);
+                content.parse()?
+            } else {
+                Expr::Lit(input.parse()?)
+            };
+            attributes.push(Attribute {
+                name: attribute_name,
+                value,
+            });
+        }
+
+        if input.peek(Token![/]) {
+            input.parse::<Token![/]>()?;
+            input.parse::<Token![>]>()?;
+            return Ok(Self {
+                name,
+                attributes,
+                children: Vec::new(),
+            });
+        }
+
+        input.parse::<Token![>]>()?;
+
+        if is_void(&name) {
+            return Ok(Self {
+                name,
+                attributes,
+                children: Vec::new(),
+            });
+        }
+
+        let mut children = Vec::new();
+        loop {
+            if input.is_empty() {
+                return Err(syn::Error::new(
+                    name.span(),
+                    format!("unclosed tag `<{name}>`"),
+                ));
+            }
+
+            if input.peek(Token![<]) && input.peek2(Token![/]) {
+                input.parse::<Token![<]>()?;
+                input.parse::<Token![/]>()?;
+                let closing: Ident = input.parse()?;
+                input.parse::<Token![>]>()?;
+
+                if closing != name {
+                    return Err(syn::Error::new(
+                        closing.span(),
+                        format!("expected `</{name}>`, found `</{closing}>`"),
+                    ));
+                }
+
+                break;
+            }
+
+            children.push(input.parse()?);
+        }
+
+        Ok(Self {
+            name,
+            attributes,
+            children,
+        })
+    }
+}
+
+struct Expander {
+    components: Path,
+}
+
+impl Expander {
+    fn nodes(&self, nodes: &[Node], span: Span) -> syn::Result<TokenStream> {
+        let nodes = nodes
+            .iter()
+            .map(|node| self.node(node))
+            .collect::<syn::Result<Vec<_>>>()?;
+
+        Ok(self.list(nodes, span))
+    }
+
+    fn list(&self, items: Vec<TokenStream>, span: Span) -> TokenStream {
+        match items.len() {
+            0 => quote_spanned!(span=> ""),
+            1 => items.into_iter().next().unwrap(),
+            len if len <= MAX_TUPLE_LEN => quote!((#(#items,)*)),
+            // nest tuples instead of boxing so phrasing children stay phrasing
+            _ => {
+                let chunks = items
+                    .chunks(MAX_TUPLE_LEN)
+                    .map(|chunk| self.list(chunk.to_vec(), span))
+                    .collect();
+                self.list(chunks, span)
+            }
+        }
+    }
+
+    fn node(&self, node: &Node) -> syn::Result<TokenStream> {
+        match node {
+            Node::Text(text) => Ok(quote!(#text)),
+            Node::Expr(expr) => Ok(quote!((#expr))),
+            Node::Element(element) => self.element(element),
+        }
+    }
+
+    fn element(&self, element: &Element) -> syn::Result<TokenStream> {
+        let components = &self.components;
+        let name = &element.name;
+        let tag = name.to_string();
+
+        if tag == "img" {
+            return self.img(element);
+        }
+
+        if let Some(attribute) = element.attributes.first() {
+            return Err(syn::Error::new(
+                attribute.name.span(),
+                format!("unknown attribute `{}` on `<{tag}>`", attribute.name),
+            ));
+        }
+
+        if tag == "br" {
+            return Ok(quote_spanned!(name.span()=> #components::Br));
+        }
+
+        if tag == "ul" {
+            return self.ul(element);
+        }
+
+        if tag == "li" {
+            return Err(syn::Error::new(
+                name.span(),
+                "`<li>` is only allowed directly inside `<ul>`",
+            ));
+        }
+
+        if !CONTAINERS.contains(&tag.as_str()) {
+            return Err(syn::Error::new(
+                name.span(),
+                format!("unknown tag `<{tag}>`"),
+            ));
+        }
+
+        let component = component_ident(name);
+        let children = self.nodes(&element.children, name.span())?;
+        Ok(quote!(#components::#component(#children)))
+    }
+
+    fn ul(&self, element: &Element) -> syn::Result<TokenStream> {
+        let components = &self.components;
+        let mut items = Vec::new();
+
+        for child in &element.children {
+            match child {
+                Node::Element(li) if li.name == "li" => {
+                    if let Some(attribute) = li.attributes.first() {
+                        return Err(syn::Error::new(
+                            attribute.name.span(),
+                            format!("unknown attribute `{}` on `<li>`", attribute.name),
+                        ));
+                    }
+                    items.push(self.nodes(&li.children, li.name.span())?);
+                }
+                Node::Element(other) => {
+                    return Err(syn::Error::new(
+                        other.name.span(),
+                        format!("expected `<li>` inside `<ul>`, found `<{}>`", other.name),
+                    ))
+                }
+                _ => {
+                    return Err(syn::Error::new(
+                        element.name.span(),
+                        "only `<li>` elements are allowed inside `<ul>`",
+                    ))
+                }
+            }
+        }
+
+        let items = match items.len() {
+            0 => quote!([""; 0]),
+            1 => {
+                let item = &items[0];
+                quote!((#item,))
+            }
+            len if len <= MAX_TUPLE_LEN => quote!((#(#items,)*)),
+            _ => quote!([#(#components::Component::to_dyn(#items)),*]),
+        };
+
+        Ok(quote!(#components::Ul(#items)))
+    }
+
+    fn img(&self, element: &Element) -> syn::Result<TokenStream> {
+        let components = &self.components;
+        let mut src = None;
+        let mut width = None;
+        let mut height = None;
+
+        for Attribute { name, value } in &element.attributes {
+            let slot = match name.to_string().as_str() {
+                "src" => &mut src,
+                "width" => &mut width,
+                "height" => &mut height,
+                _ => {
+                    return Err(syn::Error::new(
+                        name.span(),
+                        format!("unknown attribute `{name}` on `<img>`"),
+                    ))
+                }
+            };
+            *slot = Some(value);
+        }
+
+        let missing = |attribute: &str| {
+            syn::Error::new(
+                element.name.span(),
+                format!("`<img>` requires a `{attribute}` attribute"),
+            )
+        };
+        let src = src.ok_or_else(|| missing("src"))?;
+        let width = width.ok_or_else(|| missing("width"))?;
+        let height = height.ok_or_else(|| missing("height"))?;
+
+        Ok(quote! {
+            #components::Img {
+                src: #src,
+                width: #width,
+                height: #height,
+            }
+        })
+    }
+}
+
+fn component_ident(name: &Ident) -> Ident {
+    let tag = name.to_string();
+    let mut chars = tag.chars();
+    let first = chars.next().unwrap().to_ascii_uppercase();
+    format_ident!("{first}{}", chars.as_str(), span = name.span())
+}
+
+pub fn expand(input: TokenStream) -> syn::Result<TokenStream> {
+    let Input { components, nodes } = syn::parse2(input)?;
+    let expander = Expander { components };
+    expander.nodes(&nodes, Span::call_site())
+}
@@ -1,8 +1,10 @@
+extern crate self as impl_iter_tuple_kind_of;
+
 mod experiment;
 mod v1;
 mod v2;
 mod v3;
-mod v4;
+pub mod v4;
 
 use axum::{
     http::StatusCode,
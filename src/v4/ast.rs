@@ -0,0 +1,545 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use super::{
+    components::*,
+    content::AnyPhrasing,
+    forms::{Button, Checkbox, Form, Input, InputKind, Method, Select, Textarea},
+    layout::{Align, Columns, Grid, HList, Justify, Length, VStack},
+    toc::TableOfContents,
+};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Node {
+    Text {
+        text: String,
+    },
+    Html {
+        html: String,
+    },
+    Fragment {
+        children: Vec<Node>,
+    },
+    H1 {
+        children: Vec<Node>,
+    },
+    H2 {
+        children: Vec<Node>,
+    },
+    H3 {
+        children: Vec<Node>,
+    },
+    P {
+        children: Vec<Node>,
+    },
+    Ul {
+        items: Vec<Node>,
+    },
+    #[serde(rename = "hlist")]
+    HList {
+        children: Vec<Node>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        gap: Option<Length>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        align: Option<Align>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        justify: Option<Justify>,
+        #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+        wrap: bool,
+    },
+    #[serde(rename = "vstack")]
+    VStack {
+        children: Vec<Node>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        gap: Option<Length>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        align: Option<Align>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        justify: Option<Justify>,
+        #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+        wrap: bool,
+    },
+    Grid {
+        children: Vec<Node>,
+        columns: Columns,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        gap: Option<Length>,
+    },
+    Img {
+        src: String,
+        width: u32,
+        height: u32,
+    },
+    Header {
+        children: Vec<Node>,
+    },
+    Nav {
+        children: Vec<Node>,
+    },
+    Main {
+        children: Vec<Node>,
+    },
+    Section {
+        children: Vec<Node>,
+    },
+    Article {
+        children: Vec<Node>,
+    },
+    Aside {
+        children: Vec<Node>,
+    },
+    Footer {
+        children: Vec<Node>,
+    },
+    Strong {
+        children: Vec<Node>,
+    },
+    Em {
+        children: Vec<Node>,
+    },
+    Code {
+        children: Vec<Node>,
+    },
+    Span {
+        children: Vec<Node>,
+    },
+    Small {
+        children: Vec<Node>,
+    },
+    Mark {
+        children: Vec<Node>,
+    },
+    Br,
+    Form {
+        action: String,
+        method: Method,
+        children: Vec<Node>,
+    },
+    Input {
+        name: String,
+        label: String,
+        kind: InputKind,
+        value: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        error: Option<String>,
+    },
+    Textarea {
+        name: String,
+        label: String,
+        value: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        error: Option<String>,
+    },
+    Select {
+        name: String,
+        label: String,
+        options: Vec<(String, String)>,
+        selected: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        error: Option<String>,
+    },
+    Checkbox {
+        name: String,
+        label: String,
+        checked: bool,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        error: Option<String>,
+    },
+    Button {
+        children: Vec<Node>,
+    },
+    TableOfContents,
+}
+
+#[derive(Debug)]
+pub enum AstError {
+    NotPhrasing {
+        parent: &'static str,
+        child: &'static str,
+    },
+}
+
+impl fmt::Display for AstError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AstError::NotPhrasing { parent, child } => {
+                write!(
+                    f,
+                    "`{child}` is not phrasing content and cannot be placed inside `{parent}`"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for AstError {}
+
+#[derive(Debug)]
+pub enum ExportError {
+    Unsupported { component: &'static str },
+    UnsafeHtml { html: String },
+}
+
+impl fmt::Display for ExportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExportError::Unsupported { component } => {
+                write!(f, "`{component}` has no node representation")
+            }
+            ExportError::UnsafeHtml { html } => {
+                write!(f, "raw html would not survive sanitizing on import: {html}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ExportError {}
+
+macro_rules! flex {
+    ($layout:expr, $gap:expr, $align:expr, $justify:expr, $wrap:expr) => {{
+        let mut layout = $layout;
+        if let Some(gap) = $gap {
+            layout = layout.gap(gap);
+        }
+        if let Some(align) = $align {
+            layout = layout.align(align);
+        }
+        if let Some(justify) = $justify {
+            layout = layout.justify(justify);
+        }
+        if $wrap {
+            layout = layout.wrap();
+        }
+        layout.to_dyn()
+    }};
+}
+
+impl Node {
+    fn kind(&self) -> &'static str {
+        match self {
+            Node::Text { .. } => "text",
+            Node::Html { .. } => "html",
+            Node::Fragment { .. } => "fragment",
+            Node::H1 { .. } => "h1",
+            Node::H2 { .. } => "h2",
+            Node::H3 { .. } => "h3",
+            Node::P { .. } => "p",
+            Node::Ul { .. } => "ul",
+            Node::HList { .. } => "hlist",
+            Node::VStack { .. } => "vstack",
+            Node::Grid { .. } => "grid",
+            Node::Img { .. } => "img",
+            Node::Header { .. } => "header",
+            Node::Nav { .. } => "nav",
+            Node::Main { .. } => "main",
+            Node::Section { .. } => "section",
+            Node::Article { .. } => "article",
+            Node::Aside { .. } => "aside",
+            Node::Footer { .. } => "footer",
+            Node::Strong { .. } => "strong",
+            Node::Em { .. } => "em",
+            Node::Code { .. } => "code",
+            Node::Span { .. } => "span",
+            Node::Small { .. } => "small",
+            Node::Mark { .. } => "mark",
+            Node::Br => "br",
+            Node::Form { .. } => "form",
+            Node::Input { .. } => "input",
+            Node::Textarea { .. } => "textarea",
+            Node::Select { .. } => "select",
+            Node::Checkbox { .. } => "checkbox",
+            Node::Button { .. } => "button",
+            Node::TableOfContents => "table_of_contents",
+        }
+    }
+
+    fn check_phrasing(&self, parent: &'static str) -> Result<(), AstError> {
+        match self {
+            Node::Text { .. }
+            | Node::Html { .. }
+            | Node::Img { .. }
+            | Node::Strong { .. }
+            | Node::Em { .. }
+            | Node::Code { .. }
+            | Node::Span { .. }
+            | Node::Small { .. }
+            | Node::Mark { .. }
+            | Node::Br
+            | Node::Button { .. } => Ok(()),
+            Node::Fragment { children } => children
+                .iter()
+                .try_for_each(|child| child.check_phrasing(parent)),
+            node => Err(AstError::NotPhrasing {
+                parent,
+                child: node.kind(),
+            }),
+        }
+    }
+
+    pub fn into_component(self) -> Result<Box<dyn Component>, AstError> {
+        let kind = self.kind();
+
+        Ok(match self {
+            Node::Text { text } => text.to_dyn(),
+            Node::Html { html } => Raw(TrustedHtml::sanitize(&html)).to_dyn(),
+            Node::Fragment { children } => from_nodes(children)?,
+            Node::H1 { children } => H1(phrasing(children, kind)?).to_dyn(),
+            Node::H2 { children } => H2(phrasing(children, kind)?).to_dyn(),
+            Node::H3 { children } => H3(phrasing(children, kind)?).to_dyn(),
+            Node::P { children } => P(phrasing(children, kind)?).to_dyn(),
+            Node::Ul { items } => Ul(components(items)?).to_dyn(),
+            Node::HList {
+                children,
+                gap,
+                align,
+                justify,
+                wrap,
+            } => flex!(HList::new(components(children)?), gap, align, justify, wrap),
+            Node::VStack {
+                children,
+                gap,
+                align,
+                justify,
+                wrap,
+            } => flex!(
+                VStack::new(components(children)?),
+                gap,
+                align,
+                justify,
+                wrap
+            ),
+            Node::Grid {
+                children,
+                columns,
+                gap,
+            } => {
+                let grid = Grid::new(components(children)?, columns);
+                match gap {
+                    Some(gap) => grid.gap(gap).to_dyn(),
+                    None => grid.to_dyn(),
+                }
+            }
+            Node::Img { src, width, height } => Img { src, width, height }.to_dyn(),
+            Node::Header { children } => Header(from_nodes(children)?).to_dyn(),
+            Node::Nav { children } => Nav(from_nodes(children)?).to_dyn(),
+            Node::Main { children } => Main(from_nodes(children)?).to_dyn(),
+            Node::Section { children } => Section(from_nodes(children)?).to_dyn(),
+            Node::Article { children } => Article(from_nodes(children)?).to_dyn(),
+            Node::Aside { children } => Aside(from_nodes(children)?).to_dyn(),
+            Node::Footer { children } => Footer(from_nodes(children)?).to_dyn(),
+            Node::Strong { children } => Strong(phrasing(children, kind)?).to_dyn(),
+            Node::Em { children } => Em(phrasing(children, kind)?).to_dyn(),
+            Node::Code { children } => Code(phrasing(children, kind)?).to_dyn(),
+            Node::Span { children } => Span(phrasing(children, kind)?).to_dyn(),
+            Node::Small { children } => Small(phrasing(children, kind)?).to_dyn(),
+            Node::Mark { children } => Mark(phrasing(children, kind)?).to_dyn(),
+            Node::Br => Br.to_dyn(),
+            Node::Form {
+                action,
+                method,
+                children,
+            } => Form {
+                action: action.into(),
+                method,
+                children: components(children)?,
+            }
+            .to_dyn(),
+            Node::Input {
+                name,
+                label,
+                kind,
+                value,
+                error,
+            } => Input {
+                name: name.into(),
+                label: label.into(),
+                kind,
+                value,
+                error,
+            }
+            .to_dyn(),
+            Node::Textarea {
+                name,
+                label,
+                value,
+                error,
+            } => Textarea {
+                name: name.into(),
+                label: label.into(),
+                value,
+                error,
+            }
+            .to_dyn(),
+            Node::Select {
+                name,
+                label,
+                options,
+                selected,
+                error,
+            } => Select {
+                name: name.into(),
+                label: label.into(),
+                options: options
+                    .into_iter()
+                    .map(|(value, text)| (value.into(), text.into()))
+                    .collect(),
+                selected,
+                error,
+            }
+            .to_dyn(),
+            Node::Checkbox {
+                name,
+                label,
+                checked,
+                error,
+            } => Checkbox {
+                name: name.into(),
+                label: label.into(),
+                checked,
+                error,
+            }
+            .to_dyn(),
+            Node::Button { children } => Button(phrasing(children, kind)?).to_dyn(),
+            Node::TableOfContents => TableOfContents.to_dyn(),
+        })
+    }
+}
+
+fn components(nodes: Vec<Node>) -> Result<Vec<Box<dyn Component>>, AstError> {
+    nodes.into_iter().map(Node::into_component).collect()
+}
+
+fn phrasing(nodes: Vec<Node>, parent: &'static str) -> Result<AnyPhrasing, AstError> {
+    for node in &nodes {
+        node.check_phrasing(parent)?;
+    }
+    Ok(AnyPhrasing(components(nodes)?.to_dyn()))
+}
+
+pub fn from_nodes(nodes: Vec<Node>) -> Result<Box<dyn Component>, AstError> {
+    Ok(components(nodes)?.to_dyn())
+}
+
+pub fn export(component: &(impl Component + ?Sized)) -> Result<Vec<Node>, ExportError> {
+    let mut nodes = Vec::new();
+    component.export(&mut nodes)?;
+    Ok(nodes)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let page = (
+            Header((
+                HList::new((
+                    Img {
+                        src: "/public/logo.svg",
+                        width: 50,
+                        height: 50,
+                    },
+                    H2("Kansai Rust"),
+                ))
+                .gap(Length::Px(12)),
+                Nav(Ul(["chat", "talk"])),
+            )),
+            Main((
+                TableOfContents,
+                Article((
+                    H1(("Tuples", Br, Small("and iterators"))),
+                    Section((
+                        H3(Span(Mark("draft"))),
+                        P((
+                            "Use a ",
+                            Strong("tuple"),
+                            ", ",
+                            Em(Code("impl Trait")),
+                            " or ",
+                            Raw(TrustedHtml::from_static("<b>both</b>")),
+                        )),
+                    )),
+                    Grid::new([Text(1), Text(2)], Columns::Template("1fr 2fr".into()))
+                        .gap(Length::Rem(1.5)),
+                )),
+                Aside(VStack::new((Fragment(("chat", "talk")), Some("demo"), None::<&str>)).wrap()),
+            )),
+            Footer(Form {
+                action: "/v4/rsvp".into(),
+                method: Method::Post,
+                children: (
+                    Input {
+                        name: "name".into(),
+                        label: "Name".into(),
+                        kind: InputKind::Text,
+                        value: "Ferris".to_string(),
+                        error: Some("Taken".to_string()),
+                    },
+                    Textarea {
+                        name: "note".into(),
+                        label: "Note".into(),
+                        value: String::new(),
+                        error: None,
+                    },
+                    Select {
+                        name: "attendance".into(),
+                        label: "Attendance".into(),
+                        options: vec![("online".into(), "Online".into())],
+                        selected: "online".to_string(),
+                        error: None,
+                    },
+                    Checkbox {
+                        name: "subscribe".into(),
+                        label: "Subscribe".into(),
+                        checked: true,
+                        error: None,
+                    },
+                    Button("RSVP"),
+                ),
+            }),
+        );
+        let nodes = export(&page).unwrap();
+
+        let json = serde_json::to_string(&nodes).unwrap();
+        let nodes: Vec<Node> = serde_json::from_str(&json).unwrap();
+        let imported = from_nodes(nodes.clone()).unwrap();
+
+        assert_eq!(imported.render(), page.render());
+        assert_eq!(export(&imported).unwrap(), nodes);
+    }
+
+    #[test]
+    fn refuses_lossy_exports() {
+        struct Custom;
+
+        impl Component for Custom {
+            fn render_to(&self, cx: &mut crate::v4::context::RenderContext) {
+                cx.text("custom");
+            }
+        }
+
+        let svg = Raw(TrustedHtml::from_static("<svg><circle r=\"4\"/></svg>"));
+        assert!(matches!(
+            export(&P(svg)),
+            Err(ExportError::UnsafeHtml { .. })
+        ));
+        assert!(matches!(
+            export(&Main(("ok", Custom))),
+            Err(ExportError::Unsupported { component }) if component.ends_with("Custom")
+        ));
+    }
+
+    #[test]
+    fn rejects_blocks_in_paragraphs() {
+        let json = r#"[{"type": "p", "children": [{"type": "ul", "items": []}]}]"#;
+        let nodes: Vec<Node> = serde_json::from_str(json).unwrap();
+
+        assert_eq!(
+            from_nodes(nodes).err().unwrap().to_string(),
+            "`ul` is not phrasing content and cannot be placed inside `p`"
+        );
+    }
+}
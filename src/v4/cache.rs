@@ -0,0 +1,330 @@
+use std::{
+    any::TypeId,
+    cell::OnceCell,
+    collections::{hash_map::DefaultHasher, HashMap},
+    hash::{Hash, Hasher},
+    sync::{LazyLock, Mutex},
+};
+
+use super::{
+    ast::{ExportError, Node},
+    components::{Component, TrustedHtml},
+    context::RenderContext,
+    style, toc,
+};
+
+const DEFAULT_CAPACITY: usize = 256;
+
+#[derive(Clone)]
+pub(super) enum Event {
+    Enter(&'static str, &'static str),
+    Exit(&'static str),
+    Start(String),
+    Attribute(String, String),
+    End(String),
+    Text(String),
+    Raw(TrustedHtml),
+    Style(TypeId, String),
+    Heading(u8, String),
+}
+
+fn replay(cx: &mut RenderContext, events: Vec<Event>) {
+    for event in events {
+        match event {
+            Event::Enter(component, tag) => cx.enter(component, tag),
+            Event::Exit(tag) => cx.exit(tag),
+            Event::Start(tag) => cx.start_element(&tag),
+            Event::Attribute(name, value) => cx.attribute(&name, &value),
+            Event::End(tag) => cx.end_element(&tag),
+            Event::Text(text) => cx.text(&text),
+            Event::Raw(html) => cx.raw(&html),
+            Event::Style(id, css) => style::register(cx, id, css),
+            Event::Heading(level, text) => toc::heading(cx, level, text),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CacheMetrics {
+    pub hits: u64,
+    pub misses: u64,
+    pub evictions: u64,
+    pub entries: usize,
+}
+
+struct Rendered {
+    events: Vec<Event>,
+    used: u64,
+}
+
+struct Store {
+    entries: HashMap<String, Rendered>,
+    capacity: usize,
+    tick: u64,
+    hits: u64,
+    misses: u64,
+    evictions: u64,
+}
+
+impl Store {
+    fn new(capacity: usize) -> Self {
+        Self {
+            entries: HashMap::new(),
+            capacity,
+            tick: 0,
+            hits: 0,
+            misses: 0,
+            evictions: 0,
+        }
+    }
+
+    fn insert(&mut self, key: String, events: Vec<Event>) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        if !self.entries.contains_key(&key) {
+            self.evict(self.capacity - 1);
+        }
+        let used = self.tick;
+        self.entries.insert(key, Rendered { events, used });
+    }
+
+    fn evict(&mut self, capacity: usize) {
+        while self.entries.len() > capacity {
+            let Some(oldest) = self
+                .entries
+                .iter()
+                .min_by_key(|(_, rendered)| rendered.used)
+                .map(|(key, _)| key.clone())
+            else {
+                return;
+            };
+            self.entries.remove(&oldest);
+            self.evictions += 1;
+        }
+    }
+}
+
+static CACHE: LazyLock<Mutex<Store>> = LazyLock::new(|| Mutex::new(Store::new(DEFAULT_CAPACITY)));
+
+fn store() -> std::sync::MutexGuard<'static, Store> {
+    CACHE
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+fn lookup(key: &str) -> Option<Vec<Event>> {
+    let mut store = store();
+    store.tick += 1;
+    let tick = store.tick;
+
+    match store.entries.get_mut(key) {
+        Some(rendered) => {
+            rendered.used = tick;
+            let hit = rendered.events.clone();
+            store.hits += 1;
+            Some(hit)
+        }
+        None => {
+            store.misses += 1;
+            None
+        }
+    }
+}
+
+pub fn invalidate(key: &str) -> bool {
+    store().entries.remove(key).is_some()
+}
+
+pub fn clear() {
+    store().entries.clear();
+}
+
+pub fn set_capacity(capacity: usize) {
+    let mut store = store();
+    store.capacity = capacity;
+    store.evict(capacity);
+}
+
+pub fn metrics() -> CacheMetrics {
+    let store = store();
+    CacheMetrics {
+        hits: store.hits,
+        misses: store.misses,
+        evictions: store.evictions,
+        entries: store.entries.len(),
+    }
+}
+
+pub struct Cached<F, C> {
+    key: String,
+    build: F,
+    built: OnceCell<C>,
+}
+
+impl<F: Fn() -> C, C> Cached<F, C> {
+    pub fn new(key: impl Into<String>, build: F) -> Self {
+        Self {
+            key: key.into(),
+            build,
+            built: OnceCell::new(),
+        }
+    }
+
+    pub fn props<P: Hash + ?Sized>(namespace: &str, props: &P, build: F) -> Self {
+        let mut hasher = DefaultHasher::new();
+        props.hash(&mut hasher);
+        Self::new(format!("{namespace}#{:016x}", hasher.finish()), build)
+    }
+
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    fn component(&self) -> &C {
+        self.built.get_or_init(|| (self.build)())
+    }
+}
+
+impl<F: Fn() -> C, C: Component> Component for Cached<F, C> {
+    fn render_to(&self, cx: &mut RenderContext) {
+        if let Some(events) = lookup(&self.key) {
+            replay(cx, events);
+            return;
+        }
+
+        cx.start_recording();
+        self.component().render_to(cx);
+        let events = cx.finish_recording();
+        store().insert(self.key.clone(), events);
+    }
+
+    fn export(&self, nodes: &mut Vec<Node>) -> Result<(), ExportError> {
+        self.component().export(nodes)
+    }
+
+    fn tag(&self) -> Option<&'static str> {
+        self.component().tag()
+    }
+
+    fn children(&self) -> Vec<&dyn Component> {
+        self.component().children()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::cell::Cell;
+
+    use super::*;
+    use crate::v4::{
+        components::{Main, Section, H2, P},
+        render::{HtmlRenderer, Renderer},
+    };
+
+    #[derive(Component)]
+    #[component(
+        template = "<p class=\"{scope}\">{text}</p>",
+        style = "& { color: red; }"
+    )]
+    struct Note {
+        text: &'static str,
+    }
+
+    #[test]
+    fn renders_once_and_replays_effects() {
+        let builds = Cell::new(0);
+        let agenda = Cached::props("test/agenda", &1, || {
+            builds.set(builds.get() + 1);
+            Main((H2("Agenda"), Note { text: "cached" }, P("15 minutes")))
+        });
+        let before = metrics();
+
+        let first = agenda.render();
+        let mut renderer = HtmlRenderer::default();
+        let mut cx = RenderContext::new(&mut renderer);
+        agenda.render_to(&mut cx);
+        let styles = cx.styles.render();
+        let headings = cx.headings.entries.len();
+        let second = renderer.finish();
+
+        assert_eq!(first, second);
+        assert_eq!(builds.get(), 1);
+        assert!(styles.contains("{ color: red; }"));
+        assert_eq!(headings, 1);
+
+        assert!(invalidate(agenda.key()));
+        agenda.render();
+        assert_eq!(builds.get(), 1);
+
+        let after = metrics();
+        assert!(after.hits > before.hits);
+        assert!(after.misses >= before.misses + 2);
+        assert_eq!(agenda.tag(), Some("main"));
+        assert_eq!(agenda.children().len(), 1);
+    }
+
+    #[test]
+    fn walking_before_a_miss_builds_once() {
+        let builds = Cell::new(0);
+        let agenda = Cached::new("test/walk-then-render", || {
+            builds.set(builds.get() + 1);
+            Main(P("15 minutes"))
+        });
+
+        assert_eq!(agenda.children().len(), 1);
+        agenda.render();
+        assert_eq!(builds.get(), 1);
+    }
+
+    #[test]
+    fn reinserting_a_key_does_not_evict_others() {
+        let mut store = Store::new(2);
+        store.insert("a".to_string(), Vec::new());
+        store.insert("b".to_string(), Vec::new());
+        store.insert("b".to_string(), Vec::new());
+
+        assert!(store.entries.contains_key("a"));
+        assert_eq!(store.evictions, 0);
+    }
+
+    #[test]
+    fn props_keys_are_namespaced() {
+        let a = Cached::props("test/a", &1, || P("a"));
+        let b = Cached::props("test/b", &1, || P("b"));
+
+        assert_ne!(a.key(), b.key());
+        assert_eq!(a.key(), Cached::props("test/a", &1, || P("c")).key());
+    }
+
+    #[test]
+    fn replays_issue_fresh_heading_ids() {
+        let heading = Cached::new("test/heading", || H2("Agenda"));
+        let mut renderer = HtmlRenderer::default();
+        let mut cx = RenderContext::new(&mut renderer);
+        H2("Agenda").render_to(&mut cx);
+        heading.render_to(&mut cx);
+        heading.render_to(&mut cx);
+        let headings = cx.headings.entries.len();
+
+        assert_eq!(headings, 3);
+        assert_eq!(
+            renderer.finish(),
+            "<h2 id=\"agenda\">Agenda</h2><h2 id=\"agenda-1\">Agenda</h2><h2 id=\"agenda-2\">Agenda</h2>"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "<main> is not allowed inside <section> at Section > Main")]
+    #[cfg(debug_assertions)]
+    fn replays_validate_nesting() {
+        let main = Cached::new("test/nested-main", || Main("cached"));
+        main.render();
+
+        let mut renderer = HtmlRenderer::default();
+        let mut cx = RenderContext::new(&mut renderer);
+        cx.validate_nesting(true);
+        Section(main).render_to(&mut cx);
+    }
+}
@@ -1,30 +1,150 @@
-use axum::response::{Html, IntoResponse};
-pub struct Page<T>(pub T);
+use std::{any::type_name, borrow::Cow, fmt::Display};
+
+use axum::{
+    http::{header::ETAG, StatusCode},
+    response::{Html, IntoResponse},
+};
+
+use super::{
+    ast::{self, ExportError, Node},
+    content::{ListItems, Phrasing},
+    context::RenderContext,
+    etag::etag,
+    head::Head,
+    pretty,
+    render::{HtmlRenderer, Renderer},
+    toc,
+};
+
+pub use impl_iter_tuple_kind_of_macros::{Component, ComponentList};
+
+pub struct Page<T> {
+    body: T,
+    head: Head,
+    require_single_main: bool,
+    strict_nesting: bool,
+    pretty: bool,
+}
+
+impl<T> Page<T> {
+    pub fn new(body: T) -> Self {
+        Self {
+            body,
+            head: Head::new(),
+            require_single_main: false,
+            strict_nesting: false,
+            pretty: false,
+        }
+    }
+
+    pub fn head(mut self, head: Head) -> Self {
+        self.head = head;
+        self
+    }
+
+    pub fn require_single_main(mut self) -> Self {
+        self.require_single_main = true;
+        self
+    }
+
+    pub fn strict_nesting(mut self) -> Self {
+        self.strict_nesting = true;
+        self
+    }
+
+    pub fn pretty(mut self) -> Self {
+        self.pretty = true;
+        self
+    }
+}
 
 impl<T: ComponentList> IntoResponse for Page<T> {
     fn into_response(self) -> axum::response::Response {
-        let body_content = self.0.render_components();
-        let html = format!(
-            "<!Doctype html><html lang=\"en\"><head></head><body>{body_content}</body></html>"
+        let mut renderer = HtmlRenderer::default();
+        let mut cx = RenderContext::new(&mut renderer);
+        cx.validate_nesting(self.strict_nesting);
+        self.body.render_components_to(&mut cx);
+        let main_count = cx.main_count;
+        let styles = cx.styles.render();
+        let headings = cx.headings.entries;
+        let body_content = toc::fill(renderer.finish(), &headings);
+
+        if self.require_single_main && main_count != 1 {
+            tracing::error!("page rendered {main_count} <main> elements, expected exactly one");
+            return (StatusCode::INTERNAL_SERVER_ERROR, "Something went wrong...").into_response();
+        }
+
+        let head_content = format!("{}{styles}", self.head.render());
+        let mut html = format!(
+            "<!Doctype html><html lang=\"en\"><head>{head_content}</head><body>{body_content}</body></html>"
         );
-        Html(html).into_response()
+
+        if self.pretty {
+            html = pretty::pretty(&html);
+        }
+
+        ([(ETAG, etag(&html))], Html(html)).into_response()
+    }
+}
+
+impl<T: ComponentList> Page<T> {
+    pub fn render_as<R: Renderer + Default>(&self) -> String {
+        let mut collect = HtmlRenderer::default();
+        let mut cx = RenderContext::new(&mut collect);
+        self.body.render_components_to(&mut cx);
+        let headings = cx.headings.entries;
+
+        let mut renderer = R::default();
+        let mut cx = RenderContext::new(&mut renderer);
+        cx.headings.collected = Some(headings);
+        self.body.render_components_to(&mut cx);
+        renderer.finish()
+    }
+}
+
+pub trait AsDynComponent {
+    fn as_dyn(&self) -> &dyn Component;
+}
+
+impl<T: Component> AsDynComponent for T {
+    fn as_dyn(&self) -> &dyn Component {
+        self
+    }
+}
+
+impl AsDynComponent for dyn Component {
+    fn as_dyn(&self) -> &dyn Component {
+        self
     }
 }
 
 pub trait ComponentList {
-    type Item: Component + ?Sized;
+    type Item: Component + AsDynComponent + ?Sized;
     type IntoIter<'a>: Iterator<Item = &'a Self::Item>
     where
         Self: 'a;
 
     fn render_components(&self) -> String {
-        let mut content = String::new();
+        let mut renderer = HtmlRenderer::default();
+        self.render_components_to(&mut RenderContext::new(&mut renderer));
+        renderer.finish()
+    }
 
+    fn render_components_to(&self, cx: &mut RenderContext) {
         for component in self.iter_components() {
-            content.push_str(&component.render());
+            component.render_to(cx);
         }
+    }
 
-        content
+    fn export_components(&self, nodes: &mut Vec<Node>) -> Result<(), ExportError> {
+        for component in self.iter_components() {
+            component.export(nodes)?;
+        }
+        Ok(())
+    }
+
+    fn child_components(&self) -> Vec<&dyn Component> {
+        self.iter_components().map(AsDynComponent::as_dyn).collect()
     }
 
     fn iter_components<'a>(&'a self) -> Self::IntoIter<'a>;
@@ -48,18 +168,110 @@ impl<const N: usize, T: Component> ComponentList for [T; N] {
     }
 }
 
-impl<A: Component + 'static, B: Component + 'static> ComponentList for (A, B) {
-    type Item = dyn Component;
+macro_rules! impl_component_list_for_tuple {
+    ($len:literal; $($name:ident . $index:tt),+) => {
+        impl<$($name: Component + 'static),+> ComponentList for ($($name,)+) {
+            type Item = dyn Component;
 
-    type IntoIter<'a> = core::array::IntoIter<&'a (dyn Component + 'static), 2> where Self: 'a;
+            type IntoIter<'a> = core::array::IntoIter<&'a (dyn Component + 'static), $len> where Self: 'a;
 
-    fn iter_components<'a>(&'a self) -> Self::IntoIter<'a> {
-        [&self.0 as &dyn Component, &self.1].into_iter()
+            fn iter_components<'a>(&'a self) -> Self::IntoIter<'a> {
+                [$(&self.$index as &dyn Component),+].into_iter()
+            }
+        }
+
+        impl<$($name: Component + 'static),+> Component for ($($name,)+) {
+            fn render_to(&self, cx: &mut RenderContext) {
+                self.render_components_to(cx);
+            }
+
+            fn export(&self, nodes: &mut Vec<Node>) -> Result<(), ExportError> {
+                self.export_components(nodes)
+            }
+
+            fn children(&self) -> Vec<&dyn Component> {
+                self.child_components()
+            }
+        }
+    };
+}
+
+impl_component_list_for_tuple!(1; A.0);
+impl_component_list_for_tuple!(2; A.0, B.1);
+impl_component_list_for_tuple!(3; A.0, B.1, C.2);
+impl_component_list_for_tuple!(4; A.0, B.1, C.2, D.3);
+impl_component_list_for_tuple!(5; A.0, B.1, C.2, D.3, E.4);
+impl_component_list_for_tuple!(6; A.0, B.1, C.2, D.3, E.4, F.5);
+impl_component_list_for_tuple!(7; A.0, B.1, C.2, D.3, E.4, F.5, G.6);
+impl_component_list_for_tuple!(8; A.0, B.1, C.2, D.3, E.4, F.5, G.6, H.7);
+impl_component_list_for_tuple!(9; A.0, B.1, C.2, D.3, E.4, F.5, G.6, H.7, I.8);
+impl_component_list_for_tuple!(10; A.0, B.1, C.2, D.3, E.4, F.5, G.6, H.7, I.8, J.9);
+impl_component_list_for_tuple!(11; A.0, B.1, C.2, D.3, E.4, F.5, G.6, H.7, I.8, J.9, K.10);
+impl_component_list_for_tuple!(12; A.0, B.1, C.2, D.3, E.4, F.5, G.6, H.7, I.8, J.9, K.10, L.11);
+
+impl<T: Component> Component for Vec<T> {
+    fn render_to(&self, cx: &mut RenderContext) {
+        self.render_components_to(cx);
+    }
+
+    fn export(&self, nodes: &mut Vec<Node>) -> Result<(), ExportError> {
+        self.export_components(nodes)
+    }
+
+    fn children(&self) -> Vec<&dyn Component> {
+        self.child_components()
+    }
+}
+
+impl<const N: usize, T: Component> Component for [T; N] {
+    fn render_to(&self, cx: &mut RenderContext) {
+        self.render_components_to(cx);
+    }
+
+    fn export(&self, nodes: &mut Vec<Node>) -> Result<(), ExportError> {
+        self.export_components(nodes)
+    }
+
+    fn children(&self) -> Vec<&dyn Component> {
+        self.child_components()
     }
 }
 
 pub trait Component {
-    fn render(&self) -> String;
+    fn render_to(&self, cx: &mut RenderContext);
+
+    fn render(&self) -> String {
+        let mut renderer = HtmlRenderer::default();
+        self.render_to(&mut RenderContext::new(&mut renderer));
+        renderer.finish()
+    }
+
+    fn render_pretty(&self) -> String {
+        pretty::pretty(&self.render())
+    }
+
+    fn export(&self, _nodes: &mut Vec<Node>) -> Result<(), ExportError> {
+        Err(ExportError::Unsupported {
+            component: type_name::<Self>(),
+        })
+    }
+
+    fn tag(&self) -> Option<&'static str> {
+        None
+    }
+
+    fn children(&self) -> Vec<&dyn Component> {
+        Vec::new()
+    }
+
+    fn render_as<R: Renderer + Default>(&self) -> String
+    where
+        Self: Sized,
+    {
+        let mut renderer = R::default();
+        self.render_to(&mut RenderContext::new(&mut renderer));
+        renderer.finish()
+    }
 
     fn to_dyn(self) -> Box<dyn Component>
     where
@@ -69,72 +281,730 @@ pub trait Component {
     }
 }
 
-impl<'a> Component for &'a str {
-    fn render(&self) -> String {
-        html_escape::encode_text(self).to_string()
+pub struct Walk<'a> {
+    stack: Vec<&'a dyn Component>,
+}
+
+impl<'a> Iterator for Walk<'a> {
+    type Item = &'a dyn Component;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let component = self.stack.pop()?;
+        self.stack.extend(component.children().into_iter().rev());
+        Some(component)
+    }
+}
+
+pub fn walk(component: &dyn Component) -> Walk<'_> {
+    Walk {
+        stack: vec![component],
+    }
+}
+
+impl Component for () {
+    fn render_to(&self, _cx: &mut RenderContext) {}
+
+    fn export(&self, _nodes: &mut Vec<Node>) -> Result<(), ExportError> {
+        Ok(())
+    }
+}
+
+impl Component for &str {
+    fn render_to(&self, cx: &mut RenderContext) {
+        cx.text(self);
+    }
+
+    fn export(&self, nodes: &mut Vec<Node>) -> Result<(), ExportError> {
+        nodes.push(Node::Text {
+            text: self.to_string(),
+        });
+        Ok(())
+    }
+}
+
+impl Component for String {
+    fn render_to(&self, cx: &mut RenderContext) {
+        cx.text(self);
+    }
+
+    fn export(&self, nodes: &mut Vec<Node>) -> Result<(), ExportError> {
+        nodes.push(Node::Text {
+            text: self.to_string(),
+        });
+        Ok(())
+    }
+}
+
+impl Component for Cow<'_, str> {
+    fn render_to(&self, cx: &mut RenderContext) {
+        cx.text(self);
+    }
+
+    fn export(&self, nodes: &mut Vec<Node>) -> Result<(), ExportError> {
+        nodes.push(Node::Text {
+            text: self.to_string(),
+        });
+        Ok(())
+    }
+}
+
+pub struct Text<T>(pub T);
+
+impl<T: Display> Component for Text<T> {
+    fn render_to(&self, cx: &mut RenderContext) {
+        cx.text(&self.0.to_string());
+    }
+
+    fn export(&self, nodes: &mut Vec<Node>) -> Result<(), ExportError> {
+        nodes.push(Node::Text {
+            text: self.0.to_string(),
+        });
+        Ok(())
+    }
+}
+
+macro_rules! impl_component_for_display {
+    ($($ty:ty),+) => {
+        $(
+            impl Component for $ty {
+                fn render_to(&self, cx: &mut RenderContext) {
+                    Text(self).render_to(cx);
+                }
+
+                fn export(&self, nodes: &mut Vec<Node>) -> Result<(), ExportError> {
+                    Text(self).export(nodes)
+                }
+            }
+        )+
+    };
+}
+
+impl_component_for_display!(
+    char, u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64
+);
+
+#[derive(Clone)]
+pub struct TrustedHtml(Cow<'static, str>);
+
+impl TrustedHtml {
+    pub const fn from_static(html: &'static str) -> Self {
+        Self(Cow::Borrowed(html))
+    }
+
+    pub(super) fn from_template(html: String) -> Self {
+        Self(Cow::Owned(html))
+    }
+
+    pub fn sanitize(html: &str) -> Self {
+        Self(Cow::Owned(ammonia::clean(html)))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+pub struct Raw(pub TrustedHtml);
+
+impl Component for Raw {
+    fn render_to(&self, cx: &mut RenderContext) {
+        cx.raw(&self.0);
+    }
+
+    fn export(&self, nodes: &mut Vec<Node>) -> Result<(), ExportError> {
+        let html = self.0.as_str();
+        if TrustedHtml::sanitize(html).as_str() != html {
+            return Err(ExportError::UnsafeHtml {
+                html: html.to_string(),
+            });
+        }
+
+        nodes.push(Node::Html {
+            html: html.to_string(),
+        });
+        Ok(())
     }
 }
 
 impl<'a> Component for Box<dyn Component> {
-    fn render(&self) -> String {
-        Box::as_ref(&self).render()
+    fn render_to(&self, cx: &mut RenderContext) {
+        self.as_ref().render_to(cx);
+    }
+
+    fn export(&self, nodes: &mut Vec<Node>) -> Result<(), ExportError> {
+        self.as_ref().export(nodes)
+    }
+
+    fn tag(&self) -> Option<&'static str> {
+        self.as_ref().tag()
+    }
+
+    fn children(&self) -> Vec<&dyn Component> {
+        self.as_ref().children()
     }
 }
 
-pub struct Ul<T>(pub T);
+impl<T: Component> Component for Option<T> {
+    fn render_to(&self, cx: &mut RenderContext) {
+        if let Some(component) = self {
+            component.render_to(cx);
+        }
+    }
 
-impl<T: ComponentList> Component for Ul<T> {
-    fn render(&self) -> String {
-        let mut content = String::new();
+    fn export(&self, nodes: &mut Vec<Node>) -> Result<(), ExportError> {
+        match self {
+            Some(component) => component.export(nodes),
+            None => Ok(()),
+        }
+    }
+
+    fn children(&self) -> Vec<&dyn Component> {
+        match self {
+            Some(component) => vec![component as &dyn Component],
+            None => Vec::new(),
+        }
+    }
+}
+
+impl<T: Component, E: Component> Component for Result<T, E> {
+    fn render_to(&self, cx: &mut RenderContext) {
+        match self {
+            Ok(component) => component.render_to(cx),
+            Err(component) => component.render_to(cx),
+        }
+    }
 
-        for component in self.0.iter_components() {
-            content.push_str(&format!("<li>{}</li>", component.render()));
+    fn export(&self, nodes: &mut Vec<Node>) -> Result<(), ExportError> {
+        match self {
+            Ok(component) => component.export(nodes),
+            Err(component) => component.export(nodes),
         }
+    }
 
-        format!("<ul>{content}</ul>")
+    fn children(&self) -> Vec<&dyn Component> {
+        match self {
+            Ok(component) => vec![component as &dyn Component],
+            Err(component) => vec![component as &dyn Component],
+        }
     }
 }
 
-pub struct HList<T>(pub T);
+pub enum Either<L, R> {
+    Left(L),
+    Right(R),
+}
 
-impl<T: ComponentList> Component for HList<T> {
-    fn render(&self) -> String {
-        format!(
-            "<div style=\"display: flex; align-items: center;\">{}</div>",
-            self.0.render_components()
-        )
+impl<L: Component, R: Component> Component for Either<L, R> {
+    fn render_to(&self, cx: &mut RenderContext) {
+        match self {
+            Either::Left(component) => component.render_to(cx),
+            Either::Right(component) => component.render_to(cx),
+        }
+    }
+
+    fn export(&self, nodes: &mut Vec<Node>) -> Result<(), ExportError> {
+        match self {
+            Either::Left(component) => component.export(nodes),
+            Either::Right(component) => component.export(nodes),
+        }
+    }
+
+    fn children(&self) -> Vec<&dyn Component> {
+        match self {
+            Either::Left(component) => vec![component as &dyn Component],
+            Either::Right(component) => vec![component as &dyn Component],
+        }
+    }
+}
+
+pub struct Show;
+
+impl Show {
+    pub fn when<T: Component>(condition: bool, component: T) -> Option<T> {
+        condition.then_some(component)
+    }
+
+    pub fn when_else<L: Component, R: Component>(
+        condition: bool,
+        component: L,
+        otherwise: R,
+    ) -> Either<L, R> {
+        if condition {
+            Either::Left(component)
+        } else {
+            Either::Right(otherwise)
+        }
+    }
+}
+
+pub struct Fragment<T>(pub T);
+
+impl<T: ComponentList> Component for Fragment<T> {
+    fn render_to(&self, cx: &mut RenderContext) {
+        self.0.render_components_to(cx);
+    }
+
+    fn export(&self, nodes: &mut Vec<Node>) -> Result<(), ExportError> {
+        self.0.export_components(nodes)
+    }
+
+    fn children(&self) -> Vec<&dyn Component> {
+        self.0.child_components()
+    }
+}
+
+pub struct Header<T>(pub T);
+
+impl<T: Component> Component for Header<T> {
+    fn render_to(&self, cx: &mut RenderContext) {
+        cx.element("Header", "header", |cx| self.0.render_to(cx));
+    }
+
+    fn export(&self, nodes: &mut Vec<Node>) -> Result<(), ExportError> {
+        nodes.push(Node::Header {
+            children: ast::export(&self.0)?,
+        });
+        Ok(())
+    }
+
+    fn tag(&self) -> Option<&'static str> {
+        Some("header")
+    }
+
+    fn children(&self) -> Vec<&dyn Component> {
+        vec![&self.0 as &dyn Component]
+    }
+}
+
+pub struct Nav<T>(pub T);
+
+impl<T: Component> Component for Nav<T> {
+    fn render_to(&self, cx: &mut RenderContext) {
+        cx.element("Nav", "nav", |cx| self.0.render_to(cx));
+    }
+
+    fn export(&self, nodes: &mut Vec<Node>) -> Result<(), ExportError> {
+        nodes.push(Node::Nav {
+            children: ast::export(&self.0)?,
+        });
+        Ok(())
+    }
+
+    fn tag(&self) -> Option<&'static str> {
+        Some("nav")
+    }
+
+    fn children(&self) -> Vec<&dyn Component> {
+        vec![&self.0 as &dyn Component]
+    }
+}
+
+pub struct Main<T>(pub T);
+
+impl<T: Component> Component for Main<T> {
+    fn render_to(&self, cx: &mut RenderContext) {
+        cx.element("Main", "main", |cx| self.0.render_to(cx));
+    }
+
+    fn export(&self, nodes: &mut Vec<Node>) -> Result<(), ExportError> {
+        nodes.push(Node::Main {
+            children: ast::export(&self.0)?,
+        });
+        Ok(())
+    }
+
+    fn tag(&self) -> Option<&'static str> {
+        Some("main")
+    }
+
+    fn children(&self) -> Vec<&dyn Component> {
+        vec![&self.0 as &dyn Component]
+    }
+}
+
+pub struct Section<T>(pub T);
+
+impl<T: Component> Component for Section<T> {
+    fn render_to(&self, cx: &mut RenderContext) {
+        cx.element("Section", "section", |cx| self.0.render_to(cx));
+    }
+
+    fn export(&self, nodes: &mut Vec<Node>) -> Result<(), ExportError> {
+        nodes.push(Node::Section {
+            children: ast::export(&self.0)?,
+        });
+        Ok(())
+    }
+
+    fn tag(&self) -> Option<&'static str> {
+        Some("section")
+    }
+
+    fn children(&self) -> Vec<&dyn Component> {
+        vec![&self.0 as &dyn Component]
+    }
+}
+
+pub struct Article<T>(pub T);
+
+impl<T: Component> Component for Article<T> {
+    fn render_to(&self, cx: &mut RenderContext) {
+        cx.element("Article", "article", |cx| self.0.render_to(cx));
+    }
+
+    fn export(&self, nodes: &mut Vec<Node>) -> Result<(), ExportError> {
+        nodes.push(Node::Article {
+            children: ast::export(&self.0)?,
+        });
+        Ok(())
+    }
+
+    fn tag(&self) -> Option<&'static str> {
+        Some("article")
+    }
+
+    fn children(&self) -> Vec<&dyn Component> {
+        vec![&self.0 as &dyn Component]
+    }
+}
+
+pub struct Aside<T>(pub T);
+
+impl<T: Component> Component for Aside<T> {
+    fn render_to(&self, cx: &mut RenderContext) {
+        cx.element("Aside", "aside", |cx| self.0.render_to(cx));
+    }
+
+    fn export(&self, nodes: &mut Vec<Node>) -> Result<(), ExportError> {
+        nodes.push(Node::Aside {
+            children: ast::export(&self.0)?,
+        });
+        Ok(())
+    }
+
+    fn tag(&self) -> Option<&'static str> {
+        Some("aside")
+    }
+
+    fn children(&self) -> Vec<&dyn Component> {
+        vec![&self.0 as &dyn Component]
+    }
+}
+
+pub struct Footer<T>(pub T);
+
+impl<T: Component> Component for Footer<T> {
+    fn render_to(&self, cx: &mut RenderContext) {
+        cx.element("Footer", "footer", |cx| self.0.render_to(cx));
+    }
+
+    fn export(&self, nodes: &mut Vec<Node>) -> Result<(), ExportError> {
+        nodes.push(Node::Footer {
+            children: ast::export(&self.0)?,
+        });
+        Ok(())
+    }
+
+    fn tag(&self) -> Option<&'static str> {
+        Some("footer")
+    }
+
+    fn children(&self) -> Vec<&dyn Component> {
+        vec![&self.0 as &dyn Component]
+    }
+}
+
+pub struct Ul<T>(pub T);
+
+impl<T: ListItems> Component for Ul<T> {
+    fn render_to(&self, cx: &mut RenderContext) {
+        cx.element("Ul", "ul", |cx| {
+            for component in self.0.iter_components() {
+                cx.start_element("li");
+                component.render_to(cx);
+                cx.end_element("li");
+            }
+        });
+    }
+
+    fn export(&self, nodes: &mut Vec<Node>) -> Result<(), ExportError> {
+        let items = self
+            .0
+            .iter_components()
+            .map(|component| {
+                let mut item = ast::export(component)?;
+                Ok(match item.len() {
+                    1 => item.remove(0),
+                    _ => Node::Fragment { children: item },
+                })
+            })
+            .collect::<Result<_, ExportError>>()?;
+        nodes.push(Node::Ul { items });
+        Ok(())
+    }
+
+    fn tag(&self) -> Option<&'static str> {
+        Some("ul")
+    }
+
+    fn children(&self) -> Vec<&dyn Component> {
+        self.0.child_components()
     }
 }
 
 pub struct H3<T>(pub T);
 
-impl<T: Component> Component for H3<T> {
-    fn render(&self) -> String {
-        format!("<h3>{}</h3>", self.0.render())
+impl<T: Phrasing> Component for H3<T> {
+    fn render_to(&self, cx: &mut RenderContext) {
+        cx.element("H3", "h3", |cx| {
+            cx.anchor(3, |cx| self.0.render_to(cx));
+            self.0.render_to(cx);
+        });
+    }
+
+    fn export(&self, nodes: &mut Vec<Node>) -> Result<(), ExportError> {
+        nodes.push(Node::H3 {
+            children: ast::export(&self.0)?,
+        });
+        Ok(())
+    }
+
+    fn tag(&self) -> Option<&'static str> {
+        Some("h3")
+    }
+
+    fn children(&self) -> Vec<&dyn Component> {
+        vec![&self.0 as &dyn Component]
     }
 }
 
 pub struct H2<T>(pub T);
 
-impl<T: Component> Component for H2<T> {
-    fn render(&self) -> String {
-        format!("<h2>{}</h2>", self.0.render())
+impl<T: Phrasing> Component for H2<T> {
+    fn render_to(&self, cx: &mut RenderContext) {
+        cx.element("H2", "h2", |cx| {
+            cx.anchor(2, |cx| self.0.render_to(cx));
+            self.0.render_to(cx);
+        });
+    }
+
+    fn export(&self, nodes: &mut Vec<Node>) -> Result<(), ExportError> {
+        nodes.push(Node::H2 {
+            children: ast::export(&self.0)?,
+        });
+        Ok(())
+    }
+
+    fn tag(&self) -> Option<&'static str> {
+        Some("h2")
+    }
+
+    fn children(&self) -> Vec<&dyn Component> {
+        vec![&self.0 as &dyn Component]
     }
 }
 
 pub struct H1<T>(pub T);
 
-impl<T: Component> Component for H1<T> {
-    fn render(&self) -> String {
-        format!("<h1>{}</h1>", self.0.render())
+impl<T: Phrasing> Component for H1<T> {
+    fn render_to(&self, cx: &mut RenderContext) {
+        cx.element("H1", "h1", |cx| {
+            cx.anchor(1, |cx| self.0.render_to(cx));
+            self.0.render_to(cx);
+        });
+    }
+
+    fn export(&self, nodes: &mut Vec<Node>) -> Result<(), ExportError> {
+        nodes.push(Node::H1 {
+            children: ast::export(&self.0)?,
+        });
+        Ok(())
+    }
+
+    fn tag(&self) -> Option<&'static str> {
+        Some("h1")
+    }
+
+    fn children(&self) -> Vec<&dyn Component> {
+        vec![&self.0 as &dyn Component]
     }
 }
 
 pub struct P<T>(pub T);
 
-impl<T: Component> Component for P<T> {
-    fn render(&self) -> String {
-        format!("<p>{}</p>", self.0.render())
+impl<T: Phrasing> Component for P<T> {
+    fn render_to(&self, cx: &mut RenderContext) {
+        cx.element("P", "p", |cx| self.0.render_to(cx));
+    }
+
+    fn export(&self, nodes: &mut Vec<Node>) -> Result<(), ExportError> {
+        nodes.push(Node::P {
+            children: ast::export(&self.0)?,
+        });
+        Ok(())
+    }
+
+    fn tag(&self) -> Option<&'static str> {
+        Some("p")
+    }
+
+    fn children(&self) -> Vec<&dyn Component> {
+        vec![&self.0 as &dyn Component]
+    }
+}
+
+pub struct Strong<T>(pub T);
+
+impl<T: Component> Component for Strong<T> {
+    fn render_to(&self, cx: &mut RenderContext) {
+        cx.element("Strong", "strong", |cx| self.0.render_to(cx));
+    }
+
+    fn export(&self, nodes: &mut Vec<Node>) -> Result<(), ExportError> {
+        nodes.push(Node::Strong {
+            children: ast::export(&self.0)?,
+        });
+        Ok(())
+    }
+
+    fn tag(&self) -> Option<&'static str> {
+        Some("strong")
+    }
+
+    fn children(&self) -> Vec<&dyn Component> {
+        vec![&self.0 as &dyn Component]
+    }
+}
+
+pub struct Em<T>(pub T);
+
+impl<T: Component> Component for Em<T> {
+    fn render_to(&self, cx: &mut RenderContext) {
+        cx.element("Em", "em", |cx| self.0.render_to(cx));
+    }
+
+    fn export(&self, nodes: &mut Vec<Node>) -> Result<(), ExportError> {
+        nodes.push(Node::Em {
+            children: ast::export(&self.0)?,
+        });
+        Ok(())
+    }
+
+    fn tag(&self) -> Option<&'static str> {
+        Some("em")
+    }
+
+    fn children(&self) -> Vec<&dyn Component> {
+        vec![&self.0 as &dyn Component]
+    }
+}
+
+pub struct Code<T>(pub T);
+
+impl<T: Component> Component for Code<T> {
+    fn render_to(&self, cx: &mut RenderContext) {
+        cx.element("Code", "code", |cx| self.0.render_to(cx));
+    }
+
+    fn export(&self, nodes: &mut Vec<Node>) -> Result<(), ExportError> {
+        nodes.push(Node::Code {
+            children: ast::export(&self.0)?,
+        });
+        Ok(())
+    }
+
+    fn tag(&self) -> Option<&'static str> {
+        Some("code")
+    }
+
+    fn children(&self) -> Vec<&dyn Component> {
+        vec![&self.0 as &dyn Component]
+    }
+}
+
+pub struct Span<T>(pub T);
+
+impl<T: Component> Component for Span<T> {
+    fn render_to(&self, cx: &mut RenderContext) {
+        cx.element("Span", "span", |cx| self.0.render_to(cx));
+    }
+
+    fn export(&self, nodes: &mut Vec<Node>) -> Result<(), ExportError> {
+        nodes.push(Node::Span {
+            children: ast::export(&self.0)?,
+        });
+        Ok(())
+    }
+
+    fn tag(&self) -> Option<&'static str> {
+        Some("span")
+    }
+
+    fn children(&self) -> Vec<&dyn Component> {
+        vec![&self.0 as &dyn Component]
+    }
+}
+
+pub struct Small<T>(pub T);
+
+impl<T: Component> Component for Small<T> {
+    fn render_to(&self, cx: &mut RenderContext) {
+        cx.element("Small", "small", |cx| self.0.render_to(cx));
+    }
+
+    fn export(&self, nodes: &mut Vec<Node>) -> Result<(), ExportError> {
+        nodes.push(Node::Small {
+            children: ast::export(&self.0)?,
+        });
+        Ok(())
+    }
+
+    fn tag(&self) -> Option<&'static str> {
+        Some("small")
+    }
+
+    fn children(&self) -> Vec<&dyn Component> {
+        vec![&self.0 as &dyn Component]
+    }
+}
+
+pub struct Mark<T>(pub T);
+
+impl<T: Component> Component for Mark<T> {
+    fn render_to(&self, cx: &mut RenderContext) {
+        cx.element("Mark", "mark", |cx| self.0.render_to(cx));
+    }
+
+    fn export(&self, nodes: &mut Vec<Node>) -> Result<(), ExportError> {
+        nodes.push(Node::Mark {
+            children: ast::export(&self.0)?,
+        });
+        Ok(())
+    }
+
+    fn tag(&self) -> Option<&'static str> {
+        Some("mark")
+    }
+
+    fn children(&self) -> Vec<&dyn Component> {
+        vec![&self.0 as &dyn Component]
+    }
+}
+
+pub struct Br;
+
+impl Component for Br {
+    fn render_to(&self, cx: &mut RenderContext) {
+        cx.element("Br", "br", |_| {});
+    }
+
+    fn export(&self, nodes: &mut Vec<Node>) -> Result<(), ExportError> {
+        nodes.push(Node::Br);
+        Ok(())
+    }
+
+    fn tag(&self) -> Option<&'static str> {
+        Some("br")
     }
 }
 
@@ -145,9 +1015,24 @@ pub struct Img<T> {
 }
 
 impl<T: AsRef<str>> Component for Img<T> {
-    fn render(&self) -> String {
-        let Self { src, width, height } = self;
-        let src = html_escape::encode_quoted_attribute(src.as_ref());
-        format!("<img src=\"{src}\" width=\"{width}p\" height=\"{height}px\"/>",)
+    fn render_to(&self, cx: &mut RenderContext) {
+        cx.element("Img", "img", |cx| {
+            cx.attribute("src", self.src.as_ref());
+            cx.attribute("width", &self.width.to_string());
+            cx.attribute("height", &self.height.to_string());
+        });
+    }
+
+    fn export(&self, nodes: &mut Vec<Node>) -> Result<(), ExportError> {
+        nodes.push(Node::Img {
+            src: self.src.as_ref().to_string(),
+            width: self.width,
+            height: self.height,
+        });
+        Ok(())
+    }
+
+    fn tag(&self) -> Option<&'static str> {
+        Some("img")
     }
 }
@@ -0,0 +1,196 @@
+use std::borrow::Cow;
+
+use super::{
+    ast::{ExportError, Node},
+    cache::Cached,
+    components::*,
+    context::RenderContext,
+    forms::{Button, Checkbox, Form, Input, Select, Textarea},
+    layout::{Grid, HList, VStack},
+    template::Template,
+    toc::TableOfContents,
+};
+
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` is not flow content",
+    label = "expected a component that can be placed in a list item or a page section",
+    note = "implement `Flow` for your own components that render block content"
+)]
+pub trait Flow: Component {}
+
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` is not phrasing content",
+    label = "only text and inline components like `Strong`, `Code` or `Img` are allowed here",
+    note = "`P`, `H1`, `H2` and `H3` only accept phrasing content, put block components like `Ul` or `HList` next to them instead"
+)]
+pub trait Phrasing: Flow {
+    fn to_phrasing(self) -> AnyPhrasing
+    where
+        Self: Sized + 'static,
+    {
+        AnyPhrasing(self.to_dyn())
+    }
+}
+
+pub trait Heading: Flow {}
+
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` cannot be the items of a `Ul`",
+    label = "every item of a `Ul` has to be flow content",
+    note = "use a `Vec`, an array or a tuple of components"
+)]
+pub trait ListItems: ComponentList {}
+
+macro_rules! impl_phrasing {
+    ($($ty:ty),+) => {
+        $(
+            impl Flow for $ty {}
+            impl Phrasing for $ty {}
+        )+
+    };
+}
+
+impl_phrasing!(
+    (),
+    &str,
+    String,
+    Cow<'_, str>,
+    char,
+    u8,
+    u16,
+    u32,
+    u64,
+    u128,
+    usize,
+    i8,
+    i16,
+    i32,
+    i64,
+    i128,
+    isize,
+    f32,
+    f64,
+    Raw,
+    Br
+);
+
+impl Flow for Box<dyn Component> {}
+
+pub struct AnyPhrasing(pub(super) Box<dyn Component>);
+
+impl Component for AnyPhrasing {
+    fn render_to(&self, cx: &mut RenderContext) {
+        self.0.render_to(cx);
+    }
+
+    fn export(&self, nodes: &mut Vec<Node>) -> Result<(), ExportError> {
+        self.0.export(nodes)
+    }
+
+    fn tag(&self) -> Option<&'static str> {
+        self.0.tag()
+    }
+
+    fn children(&self) -> Vec<&dyn Component> {
+        self.0.children()
+    }
+}
+
+impl Flow for AnyPhrasing {}
+impl Phrasing for AnyPhrasing {}
+
+macro_rules! impl_phrasing_wrapper {
+    ($($name:ident),+) => {
+        $(
+            impl<T: Component> Flow for $name<T> {}
+            impl<T: Phrasing> Phrasing for $name<T> {}
+        )+
+    };
+}
+
+impl_phrasing_wrapper!(Strong, Em, Code, Span, Small, Mark, Button);
+
+macro_rules! impl_flow_wrapper {
+    ($($name:ident),+) => {
+        $(
+            impl<T: Component> Flow for $name<T> {}
+        )+
+    };
+}
+
+impl_flow_wrapper!(Header, Nav, Main, Section, Article, Aside, Footer);
+
+impl<T: std::fmt::Display> Flow for Text<T> {}
+impl<T: std::fmt::Display> Phrasing for Text<T> {}
+
+impl<T: AsRef<str>> Flow for Img<T> {}
+impl<T: AsRef<str>> Phrasing for Img<T> {}
+
+impl<T: Phrasing> Flow for P<T> {}
+
+impl<T: Phrasing> Flow for H1<T> {}
+impl<T: Phrasing> Heading for H1<T> {}
+impl<T: Phrasing> Flow for H2<T> {}
+impl<T: Phrasing> Heading for H2<T> {}
+impl<T: Phrasing> Flow for H3<T> {}
+impl<T: Phrasing> Heading for H3<T> {}
+
+impl<T: ListItems> Flow for Ul<T> {}
+
+impl<T: Flow> Flow for Option<T> {}
+impl<T: Phrasing> Phrasing for Option<T> {}
+
+impl<T: Flow, E: Flow> Flow for Result<T, E> {}
+impl<T: Phrasing, E: Phrasing> Phrasing for Result<T, E> {}
+
+impl<L: Flow, R: Flow> Flow for Either<L, R> {}
+impl<L: Phrasing, R: Phrasing> Phrasing for Either<L, R> {}
+
+impl<T: ComponentList + Flow> Flow for Fragment<T> {}
+impl<T: ComponentList + Phrasing> Phrasing for Fragment<T> {}
+
+impl<T: Flow> Flow for Vec<T> {}
+impl<T: Phrasing> Phrasing for Vec<T> {}
+impl<T: Flow> ListItems for Vec<T> {}
+
+impl<const N: usize, T: Flow> Flow for [T; N] {}
+impl<const N: usize, T: Phrasing> Phrasing for [T; N] {}
+impl<const N: usize, T: Flow> ListItems for [T; N] {}
+
+macro_rules! impl_content_for_tuple {
+    ($($name:ident),+) => {
+        impl<$($name: Flow + 'static),+> Flow for ($($name,)+) {}
+        impl<$($name: Phrasing + 'static),+> Phrasing for ($($name,)+) {}
+        impl<$($name: Flow + 'static),+> ListItems for ($($name,)+) {}
+    };
+}
+
+impl_content_for_tuple!(A);
+impl_content_for_tuple!(A, B);
+impl_content_for_tuple!(A, B, C);
+impl_content_for_tuple!(A, B, C, D);
+impl_content_for_tuple!(A, B, C, D, E);
+impl_content_for_tuple!(A, B, C, D, E, F);
+impl_content_for_tuple!(A, B, C, D, E, F, G);
+impl_content_for_tuple!(A, B, C, D, E, F, G, H);
+impl_content_for_tuple!(A, B, C, D, E, F, G, H, I);
+impl_content_for_tuple!(A, B, C, D, E, F, G, H, I, J);
+impl_content_for_tuple!(A, B, C, D, E, F, G, H, I, J, K);
+impl_content_for_tuple!(A, B, C, D, E, F, G, H, I, J, K, L);
+
+impl<T: ComponentList> Flow for Form<T> {}
+impl Flow for Input {}
+impl Flow for Textarea {}
+impl Flow for Select {}
+impl Flow for Checkbox {}
+
+impl<T: ComponentList> Flow for HList<T> {}
+impl<T: ComponentList> Flow for VStack<T> {}
+impl<T: ComponentList> Flow for Grid<T> {}
+
+impl Flow for Template {}
+
+impl Flow for TableOfContents {}
+
+impl<F: Fn() -> C, C: Flow> Flow for Cached<F, C> {}
+impl<F: Fn() -> C, C: Phrasing> Phrasing for Cached<F, C> {}
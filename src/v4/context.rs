@@ -0,0 +1,125 @@
+use super::{
+    cache::Event,
+    components::TrustedHtml,
+    nesting::Nesting,
+    render::{Renderer, TextRenderer},
+    style::Styles,
+    toc::{self, Headings},
+};
+
+pub struct RenderContext<'a> {
+    renderer: &'a mut dyn Renderer,
+    pub(super) main_count: usize,
+    pub(super) styles: Styles,
+    pub(super) headings: Headings,
+    nesting: Option<Nesting>,
+    recording: Vec<Vec<Event>>,
+}
+
+impl<'a> RenderContext<'a> {
+    pub fn new(renderer: &'a mut dyn Renderer) -> Self {
+        Self {
+            renderer,
+            main_count: 0,
+            styles: Styles::default(),
+            headings: Headings::default(),
+            nesting: None,
+            recording: Vec::new(),
+        }
+    }
+
+    pub(super) fn validate_nesting(&mut self, strict: bool) {
+        if cfg!(debug_assertions) {
+            self.nesting = Some(Nesting::new(strict));
+        }
+    }
+
+    pub(super) fn record(&mut self, event: Event) {
+        if let Some(events) = self.recording.last_mut() {
+            events.push(event);
+        }
+    }
+
+    pub(super) fn start_recording(&mut self) {
+        self.recording.push(Vec::new());
+    }
+
+    pub(super) fn finish_recording(&mut self) -> Vec<Event> {
+        let events = self.recording.pop().unwrap_or_default();
+        if let Some(outer) = self.recording.last_mut() {
+            outer.extend(events.iter().cloned());
+        }
+        events
+    }
+
+    pub(super) fn renderer(&mut self) -> &mut dyn Renderer {
+        self.renderer
+    }
+
+    pub fn enter(&mut self, component: &'static str, tag: &'static str) {
+        if let Some(nesting) = self.nesting.as_mut() {
+            nesting.enter(component, tag);
+        }
+        if tag == "main" {
+            self.main_count += 1;
+            self.headings.main += 1;
+        }
+
+        self.record(Event::Enter(component, tag));
+        self.renderer.start_element(tag);
+    }
+
+    pub fn exit(&mut self, tag: &'static str) {
+        self.record(Event::Exit(tag));
+        self.renderer.end_element(tag);
+
+        if tag == "main" {
+            self.headings.main -= 1;
+        }
+        if let Some(nesting) = self.nesting.as_mut() {
+            nesting.exit();
+        }
+    }
+
+    pub fn start_element(&mut self, tag: &str) {
+        self.record(Event::Start(tag.to_string()));
+        self.renderer.start_element(tag);
+    }
+
+    pub fn attribute(&mut self, name: &str, value: &str) {
+        self.record(Event::Attribute(name.to_string(), value.to_string()));
+        self.renderer.attribute(name, value);
+    }
+
+    pub fn end_element(&mut self, tag: &str) {
+        self.record(Event::End(tag.to_string()));
+        self.renderer.end_element(tag);
+    }
+
+    pub fn text(&mut self, text: &str) {
+        self.record(Event::Text(text.to_string()));
+        self.renderer.text(text);
+    }
+
+    pub fn raw(&mut self, html: &TrustedHtml) {
+        self.record(Event::Raw(html.clone()));
+        self.renderer.raw(html);
+    }
+
+    pub fn anchor(&mut self, level: u8, content: impl FnOnce(&mut RenderContext)) {
+        let mut renderer = TextRenderer::default();
+        content(&mut RenderContext::new(&mut renderer));
+        toc::heading(self, level, renderer.finish().trim().to_string());
+    }
+
+    pub fn element(
+        &mut self,
+        component: &'static str,
+        tag: &'static str,
+        content: impl FnOnce(&mut Self),
+    ) {
+        self.enter(component, tag);
+        content(self);
+        self.exit(tag);
+    }
+}
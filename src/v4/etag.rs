@@ -0,0 +1,142 @@
+use axum::{
+    http::{
+        header::{ETAG, IF_NONE_MATCH},
+        HeaderMap, HeaderValue, Method, Request, StatusCode,
+    },
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+
+// 64-bit FNV-1a, so tags stay stable across Rust releases and deploys
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+pub fn etag(body: &str) -> HeaderValue {
+    let tag = format!("\"{:x}-{:016x}\"", body.len(), fnv1a(body.as_bytes()));
+    HeaderValue::from_str(&tag).unwrap()
+}
+
+fn opaque(tag: &str) -> &str {
+    tag.trim().trim_start_matches("W/")
+}
+
+fn matches(if_none_match: &HeaderValue, etag: &HeaderValue) -> bool {
+    let (Ok(if_none_match), Ok(etag)) = (if_none_match.to_str(), etag.to_str()) else {
+        return false;
+    };
+
+    if_none_match.trim() == "*"
+        || if_none_match
+            .split(',')
+            .any(|candidate| opaque(candidate) == opaque(etag))
+}
+
+fn not_modified(headers: &HeaderMap) -> Response {
+    let mut response = StatusCode::NOT_MODIFIED.into_response();
+    if let Some(etag) = headers.get(ETAG) {
+        response.headers_mut().insert(ETAG, etag.clone());
+    }
+    response
+}
+
+pub async fn conditional_get<B>(request: Request<B>, next: Next<B>) -> Response {
+    let if_none_match = match *request.method() {
+        Method::GET | Method::HEAD => request.headers().get(IF_NONE_MATCH).cloned(),
+        _ => None,
+    };
+
+    let response = next.run(request).await;
+
+    match (if_none_match, response.headers().get(ETAG)) {
+        (Some(if_none_match), Some(etag))
+            if response.status() == StatusCode::OK && matches(&if_none_match, etag) =>
+        {
+            not_modified(response.headers())
+        }
+        _ => response,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use axum::{body::Body, middleware, routing::get, Router};
+    use tower::ServiceExt;
+
+    use super::*;
+    use crate::v4::components::{Page, P};
+
+    fn router() -> Router {
+        Router::new()
+            .route(
+                "/",
+                get(|| async { Page::new((P("Tuples"),)) })
+                    .post(|| async { Page::new((P("Tuples"),)) }),
+            )
+            .route(
+                "/missing",
+                get(|| async { (StatusCode::NOT_FOUND, [(ETAG, etag("gone"))], "gone") }),
+            )
+            .layer(middleware::from_fn(conditional_get))
+    }
+
+    async fn send(method: Method, uri: &str, if_none_match: &HeaderValue) -> Response {
+        let request = Request::builder()
+            .method(method)
+            .uri(uri)
+            .header(IF_NONE_MATCH, if_none_match)
+            .body(Body::empty())
+            .unwrap();
+        router().oneshot(request).await.unwrap()
+    }
+
+    #[test]
+    fn page_sets_a_strong_etag() {
+        let first = Page::new((P("Tuples"),)).into_response();
+        let second = Page::new((P("Tuples"),)).into_response();
+        let other = Page::new((P("Iterators"),)).into_response();
+
+        let etag = first.headers().get(ETAG).unwrap();
+        assert!(etag.to_str().unwrap().starts_with('"'));
+        assert_eq!(Some(etag), second.headers().get(ETAG));
+        assert_ne!(Some(etag), other.headers().get(ETAG));
+    }
+
+    #[test]
+    fn if_none_match() {
+        let etag = etag("<p>Tuples</p>");
+        let weak = HeaderValue::from_str(&format!("\"other\", W/{}", etag.to_str().unwrap()));
+
+        assert!(matches(&etag, &etag));
+        assert!(matches(&weak.unwrap(), &etag));
+        assert!(matches(&HeaderValue::from_static("*"), &etag));
+        assert!(!matches(&HeaderValue::from_static("\"other\""), &etag));
+    }
+
+    #[test]
+    fn etags_use_a_fixed_hash() {
+        assert_eq!(etag(""), "\"0-cbf29ce484222325\"");
+        assert_eq!(etag("a"), "\"1-af63dc4c8601ec8c\"");
+    }
+
+    #[tokio::test]
+    async fn conditional_get_middleware() {
+        let page = Page::new((P("Tuples"),)).into_response();
+        let etag = page.headers().get(ETAG).unwrap().clone();
+
+        let cached = send(Method::GET, "/", &etag).await;
+        assert_eq!(cached.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(cached.headers().get(ETAG), Some(&etag));
+
+        let stale = send(Method::GET, "/", &HeaderValue::from_static("\"other\"")).await;
+        assert_eq!(stale.status(), StatusCode::OK);
+
+        let post = send(Method::POST, "/", &etag).await;
+        assert_eq!(post.status(), StatusCode::OK);
+
+        let missing = send(Method::GET, "/missing", &HeaderValue::from_static("*")).await;
+        assert_eq!(missing.status(), StatusCode::NOT_FOUND);
+    }
+}
@@ -0,0 +1,484 @@
+use std::borrow::Cow;
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use super::{
+    ast::{self, ExportError, Node},
+    components::{Component, ComponentList},
+    context::RenderContext,
+};
+
+pub use impl_iter_tuple_kind_of_macros::FormData;
+
+pub trait FormField: Copy + PartialEq + 'static {
+    const ALL: &'static [Self];
+
+    fn name(self) -> &'static str;
+}
+
+pub trait FormData: DeserializeOwned + Default {
+    type Field: FormField;
+
+    fn value(&self, field: Self::Field) -> String;
+}
+
+pub trait Validate: FormData {
+    fn validate(&self) -> FieldErrors<Self::Field> {
+        FieldErrors::new()
+    }
+}
+
+pub struct FieldErrors<F>(Vec<(F, String)>);
+
+impl<F: FormField> FieldErrors<F> {
+    pub fn new() -> Self {
+        Self(Vec::new())
+    }
+
+    pub fn push(&mut self, field: F, message: impl Into<String>) {
+        self.0.push((field, message.into()));
+    }
+
+    pub fn get(&self, field: F) -> Option<&str> {
+        self.0
+            .iter()
+            .find(|(f, _)| *f == field)
+            .map(|(_, message)| message.as_str())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl<F: FormField> Default for FieldErrors<F> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub struct FormState<T: FormData> {
+    data: T,
+    errors: FieldErrors<T::Field>,
+}
+
+impl<T: FormData> FormState<T> {
+    pub fn new() -> Self {
+        Self {
+            data: T::default(),
+            errors: FieldErrors::new(),
+        }
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    pub fn data(&self) -> &T {
+        &self.data
+    }
+
+    pub fn into_data(self) -> T {
+        self.data
+    }
+
+    fn error(&self, field: T::Field) -> Option<String> {
+        self.errors.get(field).map(str::to_string)
+    }
+
+    pub fn input(&self, field: T::Field, label: &'static str, kind: InputKind) -> Input {
+        Input {
+            name: field.name().into(),
+            label: label.into(),
+            kind,
+            value: self.data.value(field),
+            error: self.error(field),
+        }
+    }
+
+    pub fn textarea(&self, field: T::Field, label: &'static str) -> Textarea {
+        Textarea {
+            name: field.name().into(),
+            label: label.into(),
+            value: self.data.value(field),
+            error: self.error(field),
+        }
+    }
+
+    pub fn select(
+        &self,
+        field: T::Field,
+        label: &'static str,
+        options: Vec<(&'static str, &'static str)>,
+    ) -> Select {
+        Select {
+            name: field.name().into(),
+            label: label.into(),
+            options: options
+                .into_iter()
+                .map(|(value, text)| (value.into(), text.into()))
+                .collect(),
+            selected: self.data.value(field),
+            error: self.error(field),
+        }
+    }
+
+    pub fn checkbox(&self, field: T::Field, label: &'static str) -> Checkbox {
+        Checkbox {
+            name: field.name().into(),
+            label: label.into(),
+            checked: self.data.value(field) == "true",
+            error: self.error(field),
+        }
+    }
+}
+
+impl<T: Validate> FormState<T> {
+    pub fn submitted(data: T) -> Self {
+        let errors = data.validate();
+        Self { data, errors }
+    }
+}
+
+impl<T: FormData> Default for FormState<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn field(
+    cx: &mut RenderContext,
+    component: &'static str,
+    label: &str,
+    error: &Option<String>,
+    control: impl FnOnce(&mut RenderContext),
+) {
+    cx.element(component, "div", |cx| {
+        cx.attribute("class", "field");
+        cx.start_element("label");
+        cx.text(label);
+        control(cx);
+        cx.end_element("label");
+
+        if let Some(message) = error {
+            cx.start_element("p");
+            cx.attribute("class", "field-error");
+            cx.text(message);
+            cx.end_element("p");
+        }
+    });
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Method {
+    Get,
+    Post,
+}
+
+impl Method {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Method::Get => "get",
+            Method::Post => "post",
+        }
+    }
+}
+
+pub struct Form<T> {
+    pub action: Cow<'static, str>,
+    pub method: Method,
+    pub children: T,
+}
+
+impl<T: ComponentList> Component for Form<T> {
+    fn render_to(&self, cx: &mut RenderContext) {
+        cx.element("Form", "form", |cx| {
+            cx.attribute("action", &self.action);
+            cx.attribute("method", self.method.as_str());
+            self.children.render_components_to(cx);
+        });
+    }
+
+    fn export(&self, nodes: &mut Vec<Node>) -> Result<(), ExportError> {
+        let mut children = Vec::new();
+        self.children.export_components(&mut children)?;
+        nodes.push(Node::Form {
+            action: self.action.to_string(),
+            method: self.method,
+            children,
+        });
+        Ok(())
+    }
+
+    fn tag(&self) -> Option<&'static str> {
+        Some("form")
+    }
+
+    fn children(&self) -> Vec<&dyn Component> {
+        self.children.child_components()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InputKind {
+    Text,
+    Email,
+    Number,
+}
+
+impl InputKind {
+    fn as_str(&self) -> &'static str {
+        match self {
+            InputKind::Text => "text",
+            InputKind::Email => "email",
+            InputKind::Number => "number",
+        }
+    }
+}
+
+pub struct Input {
+    pub name: Cow<'static, str>,
+    pub label: Cow<'static, str>,
+    pub kind: InputKind,
+    pub value: String,
+    pub error: Option<String>,
+}
+
+impl Component for Input {
+    fn render_to(&self, cx: &mut RenderContext) {
+        field(cx, "Input", &self.label, &self.error, |cx| {
+            cx.start_element("input");
+            cx.attribute("type", self.kind.as_str());
+            cx.attribute("name", &self.name);
+            cx.attribute("value", &self.value);
+            cx.end_element("input");
+        });
+    }
+
+    fn export(&self, nodes: &mut Vec<Node>) -> Result<(), ExportError> {
+        nodes.push(Node::Input {
+            name: self.name.to_string(),
+            label: self.label.to_string(),
+            kind: self.kind,
+            value: self.value.clone(),
+            error: self.error.clone(),
+        });
+        Ok(())
+    }
+}
+
+pub struct Textarea {
+    pub name: Cow<'static, str>,
+    pub label: Cow<'static, str>,
+    pub value: String,
+    pub error: Option<String>,
+}
+
+impl Component for Textarea {
+    fn render_to(&self, cx: &mut RenderContext) {
+        field(cx, "Textarea", &self.label, &self.error, |cx| {
+            cx.start_element("textarea");
+            cx.attribute("name", &self.name);
+            cx.text(&self.value);
+            cx.end_element("textarea");
+        });
+    }
+
+    fn export(&self, nodes: &mut Vec<Node>) -> Result<(), ExportError> {
+        nodes.push(Node::Textarea {
+            name: self.name.to_string(),
+            label: self.label.to_string(),
+            value: self.value.clone(),
+            error: self.error.clone(),
+        });
+        Ok(())
+    }
+}
+
+pub struct Select {
+    pub name: Cow<'static, str>,
+    pub label: Cow<'static, str>,
+    pub options: Vec<(Cow<'static, str>, Cow<'static, str>)>,
+    pub selected: String,
+    pub error: Option<String>,
+}
+
+impl Component for Select {
+    fn render_to(&self, cx: &mut RenderContext) {
+        field(cx, "Select", &self.label, &self.error, |cx| {
+            cx.start_element("select");
+            cx.attribute("name", &self.name);
+
+            for (value, text) in &self.options {
+                cx.start_element("option");
+                cx.attribute("value", value);
+                if *value == self.selected {
+                    cx.attribute("selected", "");
+                }
+                cx.text(text);
+                cx.end_element("option");
+            }
+
+            cx.end_element("select");
+        });
+    }
+
+    fn export(&self, nodes: &mut Vec<Node>) -> Result<(), ExportError> {
+        nodes.push(Node::Select {
+            name: self.name.to_string(),
+            label: self.label.to_string(),
+            options: self
+                .options
+                .iter()
+                .map(|(value, text)| (value.to_string(), text.to_string()))
+                .collect(),
+            selected: self.selected.clone(),
+            error: self.error.clone(),
+        });
+        Ok(())
+    }
+}
+
+pub struct Checkbox {
+    pub name: Cow<'static, str>,
+    pub label: Cow<'static, str>,
+    pub checked: bool,
+    pub error: Option<String>,
+}
+
+impl Component for Checkbox {
+    fn render_to(&self, cx: &mut RenderContext) {
+        field(cx, "Checkbox", &self.label, &self.error, |cx| {
+            cx.start_element("input");
+            cx.attribute("type", "checkbox");
+            cx.attribute("name", &self.name);
+            cx.attribute("value", "true");
+            if self.checked {
+                cx.attribute("checked", "");
+            }
+            cx.end_element("input");
+        });
+    }
+
+    fn export(&self, nodes: &mut Vec<Node>) -> Result<(), ExportError> {
+        nodes.push(Node::Checkbox {
+            name: self.name.to_string(),
+            label: self.label.to_string(),
+            checked: self.checked,
+            error: self.error.clone(),
+        });
+        Ok(())
+    }
+}
+
+#[derive(Component)]
+pub enum Control {
+    Input(Input),
+    Textarea(Textarea),
+    Select(Select),
+    Checkbox(Checkbox),
+}
+
+pub struct Button<T>(pub T);
+
+impl<T: Component> Component for Button<T> {
+    fn render_to(&self, cx: &mut RenderContext) {
+        cx.element("Button", "button", |cx| {
+            cx.attribute("type", "submit");
+            self.0.render_to(cx);
+        });
+    }
+
+    fn export(&self, nodes: &mut Vec<Node>) -> Result<(), ExportError> {
+        nodes.push(Node::Button {
+            children: ast::export(&self.0)?,
+        });
+        Ok(())
+    }
+
+    fn tag(&self) -> Option<&'static str> {
+        Some("button")
+    }
+
+    fn children(&self) -> Vec<&dyn Component> {
+        vec![&self.0 as &dyn Component]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[derive(Default, Deserialize, FormData)]
+    #[serde(default, rename_all = "kebab-case")]
+    struct Signup {
+        name: String,
+        #[serde(rename = "newsletter")]
+        subscribe: bool,
+        referred_by: String,
+        #[serde(skip)]
+        confirmed: bool,
+    }
+
+    impl Validate for Signup {
+        fn validate(&self) -> FieldErrors<Self::Field> {
+            let mut errors = FieldErrors::new();
+            if self.name.trim().is_empty() {
+                errors.push(SignupField::Name, "Please enter a name");
+            }
+            errors
+        }
+    }
+
+    #[test]
+    fn field_names_match_serde() {
+        assert_eq!(
+            SignupField::ALL
+                .iter()
+                .map(|field| field.name())
+                .collect::<Vec<_>>(),
+            ["name", "newsletter", "referred-by"]
+        );
+
+        for &field in SignupField::ALL {
+            let data: Signup =
+                serde_urlencoded::from_str(&format!("{}=true", field.name())).unwrap();
+            assert_eq!(data.value(field), "true");
+        }
+        assert!(!Signup::default().confirmed);
+    }
+
+    #[test]
+    fn round_trip() {
+        let data: Signup = serde_urlencoded::from_str("name=Ferris&newsletter=true").unwrap();
+        let state = FormState::submitted(data);
+
+        assert!(state.is_valid());
+        assert_eq!(
+            state.input(SignupField::Name, "Name", InputKind::Text).render(),
+            "<div class=\"field\"><label>Name<input type=\"text\" name=\"name\" value=\"Ferris\"/></label></div>"
+        );
+        assert!(state
+            .checkbox(SignupField::Subscribe, "Subscribe")
+            .render()
+            .contains("name=\"newsletter\" value=\"true\" checked=\"\""));
+    }
+
+    #[test]
+    fn errors_next_to_field() {
+        let data: Signup = serde_urlencoded::from_str("name=+").unwrap();
+        let state = FormState::submitted(data);
+
+        assert!(!state.is_valid());
+        assert!(state
+            .input(SignupField::Name, "Name", InputKind::Text)
+            .render()
+            .ends_with("<p class=\"field-error\">Please enter a name</p></div>"));
+        assert!(!state
+            .checkbox(SignupField::Subscribe, "Subscribe")
+            .render()
+            .contains("field-error"));
+    }
+}
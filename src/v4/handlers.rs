@@ -1,51 +1,250 @@
-use super::components::*;
-use axum::{response::IntoResponse, routing::get, Router};
+use super::{
+    cache::Cached,
+    components::*,
+    etag::{conditional_get, etag},
+    forms::*,
+    head::Head,
+    layout::*,
+    render::{MarkdownRenderer, TextRenderer},
+    template::*,
+    toc::TableOfContents,
+};
+use crate::html;
+use axum::{
+    extract::State,
+    http::{header, HeaderValue},
+    middleware,
+    response::IntoResponse,
+    routing::get,
+    Router,
+};
+use serde::Deserialize;
+use std::sync::LazyLock;
+
+static BASE_URL: LazyLock<String> = LazyLock::new(|| {
+    std::env::var("BASE_URL").unwrap_or_else(|_| "http://127.0.0.1:3000".to_string())
+});
 
 pub fn router() -> Router {
-    Router::new().route("/", get(index))
+    let venue = TemplateSource::parse(
+        "venue.html",
+        include_str!("templates/venue.html"),
+        &["venue", "room"],
+    )
+    .unwrap_or_else(|err| panic!("{err}"));
+
+    Router::new()
+        .route("/", get(index))
+        .route("/index.md", get(index_markdown))
+        .route("/index.txt", get(index_text))
+        .route("/rsvp", get(rsvp).post(submit_rsvp))
+        .with_state(venue)
+        .layer(middleware::from_fn(conditional_get))
 }
 
-async fn index() -> impl IntoResponse {
-    Page([
-        header().to_dyn(),
+async fn index(State(venue): State<TemplateSource>) -> impl IntoResponse {
+    event(&venue)
+}
+
+async fn index_markdown(State(venue): State<TemplateSource>) -> impl IntoResponse {
+    let markdown = event(&venue).render_as::<MarkdownRenderer>();
+    (
+        [
+            (
+                header::CONTENT_TYPE,
+                HeaderValue::from_static("text/markdown; charset=utf-8"),
+            ),
+            (header::ETAG, etag(&markdown)),
+        ],
+        markdown,
+    )
+}
+
+async fn index_text(State(venue): State<TemplateSource>) -> impl IntoResponse {
+    let text = event(&venue).render_as::<TextRenderer>();
+    (
+        [
+            (
+                header::CONTENT_TYPE,
+                HeaderValue::from_static("text/plain; charset=utf-8"),
+            ),
+            (header::ETAG, etag(&text)),
+        ],
+        text,
+    )
+}
+
+fn event(venue: &TemplateSource) -> Page<impl ComponentList> {
+    layout(head(
+        "How to implement Iterator on tuples... kind of",
+        "/v4/",
+    ))
+    .main([
         H1("Rust talk: How to implement Iterator on tuples... kind of").to_dyn(),
+        TableOfContents.to_dyn(),
         H2("Details").to_dyn(),
-        P("Have you ever wanted to iterate over a collection of types
+        P((
+            "Have you ever wanted to iterate over a collection of types
             that share the same trait? You could put them in an array or 
             a vector but than you would have to convert them into a trait
-            object first. What a hassle! What if I told you you can use a 
-            tuple instead?")
+            object like ",
+            Code("Box<dyn Component>"),
+            " first. ",
+            Em("What a hassle!"),
+            " What if I told you you can use a ",
+            Strong("tuple"),
+            " instead?",
+        ))
         .to_dyn(),
-        P(
-            "Andrew Safigan (Software Engineer at NEI Japan) will give a talk
-            about implementing iterators on tuples in Rust. Join us to learn 
-            about the benefits and challenges of trying to do so.",
-        )
-        .to_dyn(),
-        P(
-            "The event is being held at Kyoto City Higashiyama Iki-Iki Citizens’
-            Activity Centre in Room 102. It is about 6 minutes walk for Sanjo Station.",
-        )
+        Cached::new("v4/speaker", || Speaker {
+            name: "Andrew Safigan",
+            role: "Software Engineer at NEI Japan",
+        })
         .to_dyn(),
-        H3("Agenda").to_dyn(),
-        Ul(vec![
-            "15 minutes open chat",
-            "30 minutes presentation & questions",
-            "15 minutes open chat",
-        ])
+        venue
+            .template()
+            .slot(
+                "venue",
+                "Kyoto City Higashiyama Iki-Iki Citizens’ Activity Centre",
+            )
+            .slot("room", "Room 102")
+            .build()
+            .expect("venue template slots are filled")
+            .to_dyn(),
+        Cached::new("v4/agenda", || {
+            html! {
+                <h3>"Agenda"</h3>
+                <ul>
+                    <li>"15 minutes open chat"</li>
+                    <li>"30 minutes presentation & questions"</li>
+                    <li>"15 minutes open chat"</li>
+                </ul>
+            }
+        })
         .to_dyn(),
         P("After the event you are welcome go to a local cafe and talk casually with others.")
             .to_dyn(),
     ])
+    .into_page()
 }
 
-fn header() -> impl Component {
-    HList((
-        Img {
-            src: "/public/logo.svg",
-            width: 50,
-            height: 50,
-        },
-        H2("Kansai Rust"),
-    ))
+#[derive(Component)]
+#[component(
+    template = "<p class=\"{scope}\"><strong>{name}</strong> ({role}) will give a talk about \
+    implementing iterators on tuples in Rust. Join us to learn about the benefits and \
+    challenges of trying to do so.</p>",
+    style = "& { padding-left: 1rem; border-left: 4px solid #dea584; }"
+)]
+struct Speaker {
+    name: &'static str,
+    role: &'static str,
+}
+
+fn head(title: &str, path: &str) -> Head {
+    Head::new()
+        .base_url(BASE_URL.as_str())
+        .title(format!("{title} | Kansai Rust"))
+        .description(
+            "Kansai Rust meetup in Kyoto: a talk on implementing iterators on tuples in Rust.",
+        )
+        .canonical(path)
+        .open_graph("og:site_name", "Kansai Rust")
+        .open_graph("og:title", title)
+        .open_graph("og:url", path)
+        .open_graph("og:image", "/public/logo.svg")
+}
+
+fn layout(head: Head) -> Layout<impl Component, MissingMain, impl Component> {
+    Layout::new()
+        .head(head)
+        .header(
+            HList::new(html! {
+                <img src="/public/logo.svg" width=50 height=50 />
+                <h2>"Kansai Rust"</h2>
+            })
+            .gap(Length::Px(12)),
+        )
+        .footer(html! { <small>"Kansai Rust meetup"</small> })
+}
+
+#[derive(Default, Deserialize, FormData)]
+#[serde(default)]
+struct Rsvp {
+    name: String,
+    email: String,
+    attendance: String,
+    comment: String,
+    cafe: bool,
+}
+
+impl Validate for Rsvp {
+    fn validate(&self) -> FieldErrors<Self::Field> {
+        let mut errors = FieldErrors::new();
+
+        if self.name.trim().is_empty() {
+            errors.push(RsvpField::Name, "Please tell us your name.");
+        }
+        if !self.email.contains('@') {
+            errors.push(RsvpField::Email, "Please enter a valid email address.");
+        }
+        if !matches!(self.attendance.as_str(), "in-person" | "online") {
+            errors.push(RsvpField::Attendance, "Please choose how you will attend.");
+        }
+
+        errors
+    }
+}
+
+async fn rsvp() -> impl IntoResponse {
+    rsvp_page(&FormState::new())
+}
+
+async fn submit_rsvp(axum::Form(rsvp): axum::Form<Rsvp>) -> impl IntoResponse {
+    let state = FormState::submitted(rsvp);
+
+    if state.is_valid() {
+        let name = state.into_data().name;
+        return layout(head("Thanks for your RSVP!", "/v4/rsvp"))
+            .main((
+                H1("Thanks for your RSVP!"),
+                P(format!("See you there, {name}.")),
+            ))
+            .into_page()
+            .into_response();
+    }
+
+    rsvp_page(&state).into_response()
+}
+
+fn rsvp_page(state: &FormState<Rsvp>) -> impl IntoResponse {
+    layout(head("RSVP", "/v4/rsvp"))
+        .main((
+            H1("RSVP"),
+            Form {
+                action: "/v4/rsvp".into(),
+                method: Method::Post,
+                children: (
+                    Show::when(!state.is_valid(), P("Please fix the highlighted fields.")),
+                    [
+                        Control::Input(state.input(RsvpField::Name, "Name", InputKind::Text)),
+                        Control::Input(state.input(RsvpField::Email, "Email", InputKind::Email)),
+                        Control::Select(state.select(
+                            RsvpField::Attendance,
+                            "Attendance",
+                            vec![
+                                ("", "Choose one"),
+                                ("in-person", "In person"),
+                                ("online", "Online"),
+                            ],
+                        )),
+                        Control::Textarea(state.textarea(RsvpField::Comment, "Comment")),
+                        Control::Checkbox(
+                            state.checkbox(RsvpField::Cafe, "Join us at the cafe afterwards"),
+                        ),
+                    ],
+                    Button("Send RSVP"),
+                ),
+            },
+        ))
+        .into_page()
 }
@@ -0,0 +1,202 @@
+use std::borrow::Cow;
+
+use super::{components::Component, context::RenderContext};
+
+const URL_PROPERTIES: &[&str] = &["og:url", "og:image", "og:video", "og:audio"];
+
+pub struct Head {
+    base_url: Option<String>,
+    title: Option<String>,
+    viewport: String,
+    description: Option<String>,
+    canonical: Option<String>,
+    open_graph: Vec<(String, String)>,
+    stylesheets: Vec<String>,
+    scripts: Vec<String>,
+}
+
+impl Head {
+    pub fn new() -> Self {
+        Self {
+            base_url: None,
+            title: None,
+            viewport: "width=device-width, initial-scale=1".to_string(),
+            description: None,
+            canonical: None,
+            open_graph: Vec::new(),
+            stylesheets: Vec::new(),
+            scripts: Vec::new(),
+        }
+    }
+
+    pub fn base_url(mut self, url: impl Into<String>) -> Self {
+        let url = url.into();
+        self.base_url = Some(url.trim_end_matches('/').to_string());
+        self
+    }
+
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    pub fn viewport(mut self, viewport: impl Into<String>) -> Self {
+        self.viewport = viewport.into();
+        self
+    }
+
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    pub fn canonical(mut self, href: impl Into<String>) -> Self {
+        self.canonical = Some(href.into());
+        self
+    }
+
+    pub fn open_graph(mut self, property: impl Into<String>, content: impl Into<String>) -> Self {
+        let property = property.into();
+        let content = content.into();
+
+        if !self
+            .open_graph
+            .iter()
+            .any(|(p, c)| *p == property && *c == content)
+        {
+            self.open_graph.push((property, content));
+        }
+
+        self
+    }
+
+    pub fn stylesheet(mut self, href: impl Into<String>) -> Self {
+        let href = href.into();
+        if !self.stylesheets.contains(&href) {
+            self.stylesheets.push(href);
+        }
+        self
+    }
+
+    pub fn script(mut self, src: impl Into<String>) -> Self {
+        let src = src.into();
+        if !self.scripts.contains(&src) {
+            self.scripts.push(src);
+        }
+        self
+    }
+
+    fn absolute<'a>(&self, href: &'a str) -> Cow<'a, str> {
+        match &self.base_url {
+            Some(base) if href.starts_with('/') && !href.starts_with("//") => {
+                Cow::Owned(format!("{base}{href}"))
+            }
+            _ => Cow::Borrowed(href),
+        }
+    }
+}
+
+impl Default for Head {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn meta(cx: &mut RenderContext, attributes: &[(&str, &str)]) {
+    cx.start_element("meta");
+    for (name, value) in attributes {
+        cx.attribute(name, value);
+    }
+    cx.end_element("meta");
+}
+
+fn link(cx: &mut RenderContext, rel: &str, href: &str) {
+    cx.start_element("link");
+    cx.attribute("rel", rel);
+    cx.attribute("href", href);
+    cx.end_element("link");
+}
+
+impl Component for Head {
+    fn render_to(&self, cx: &mut RenderContext) {
+        meta(cx, &[("charset", "utf-8")]);
+        meta(cx, &[("name", "viewport"), ("content", &self.viewport)]);
+
+        if let Some(title) = &self.title {
+            cx.start_element("title");
+            cx.text(title);
+            cx.end_element("title");
+        }
+
+        if let Some(description) = &self.description {
+            meta(cx, &[("name", "description"), ("content", description)]);
+        }
+
+        if let Some(canonical) = &self.canonical {
+            link(cx, "canonical", &self.absolute(canonical));
+        }
+
+        for (property, value) in &self.open_graph {
+            let value = if URL_PROPERTIES
+                .iter()
+                .any(|url| property == url || property.starts_with(&format!("{url}:")))
+            {
+                self.absolute(value)
+            } else {
+                Cow::Borrowed(value.as_str())
+            };
+            meta(cx, &[("property", property), ("content", &value)]);
+        }
+
+        for href in &self.stylesheets {
+            link(cx, "stylesheet", href);
+        }
+
+        for src in &self.scripts {
+            cx.start_element("script");
+            cx.attribute("src", src);
+            cx.attribute("defer", "");
+            cx.end_element("script");
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn escapes_and_deduplicates() {
+        let head = Head::new()
+            .title("Tuples & <Iterators>")
+            .open_graph("og:title", "\"quoted\"")
+            .open_graph("og:title", "\"quoted\"")
+            .open_graph("og:image", "/public/first.png")
+            .open_graph("og:image", "/public/second.png")
+            .stylesheet("/public/site.css")
+            .stylesheet("/public/site.css");
+        let html = head.render();
+
+        assert!(html.contains("<title>Tuples &amp; &lt;Iterators&gt;</title>"));
+        assert!(html.contains("<meta property=\"og:title\" content=\"&quot;quoted&quot;\"/>"));
+        assert_eq!(html.matches("og:title").count(), 1);
+        assert!(html.contains("first.png") && html.contains("second.png"));
+        assert_eq!(html.matches("site.css").count(), 1);
+    }
+
+    #[test]
+    fn resolves_urls_against_base_url() {
+        let html = Head::new()
+            .base_url("https://kansai-rust.example/")
+            .canonical("/v4/")
+            .open_graph("og:title", "/not/a/url")
+            .open_graph("og:image", "/public/logo.svg")
+            .open_graph("og:image:secure_url", "https://cdn.example/logo.svg")
+            .render();
+
+        assert!(html.contains("<link rel=\"canonical\" href=\"https://kansai-rust.example/v4/\"/>"));
+        assert!(html.contains("content=\"/not/a/url\""));
+        assert!(html.contains("content=\"https://kansai-rust.example/public/logo.svg\""));
+        assert!(html.contains("content=\"https://cdn.example/logo.svg\""));
+    }
+}
@@ -0,0 +1,375 @@
+use std::{borrow::Cow, fmt};
+
+use serde::{Deserialize, Serialize};
+
+use super::{
+    ast::{ExportError, Node},
+    components::{Component, ComponentList, Footer, Header, Main, Page},
+    context::RenderContext,
+    head::Head,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Length {
+    Px(u32),
+    Rem(f32),
+}
+
+impl fmt::Display for Length {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Length::Px(value) => write!(f, "{value}px"),
+            Length::Rem(value) => write!(f, "{value}rem"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Align {
+    Start,
+    Center,
+    End,
+    Stretch,
+    Baseline,
+}
+
+impl Align {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Align::Start => "flex-start",
+            Align::Center => "center",
+            Align::End => "flex-end",
+            Align::Stretch => "stretch",
+            Align::Baseline => "baseline",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Justify {
+    Start,
+    Center,
+    End,
+    SpaceBetween,
+    SpaceAround,
+    SpaceEvenly,
+}
+
+impl Justify {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Justify::Start => "flex-start",
+            Justify::Center => "center",
+            Justify::End => "flex-end",
+            Justify::SpaceBetween => "space-between",
+            Justify::SpaceAround => "space-around",
+            Justify::SpaceEvenly => "space-evenly",
+        }
+    }
+}
+
+struct Flex {
+    column: bool,
+    gap: Option<Length>,
+    align: Option<Align>,
+    justify: Option<Justify>,
+    wrap: bool,
+}
+
+impl Flex {
+    fn style(&self) -> String {
+        let mut style = String::from("display: flex;");
+
+        if self.column {
+            style.push_str(" flex-direction: column;");
+        }
+        if let Some(align) = self.align {
+            style.push_str(&format!(" align-items: {};", align.as_str()));
+        }
+        if let Some(justify) = self.justify {
+            style.push_str(&format!(" justify-content: {};", justify.as_str()));
+        }
+        if let Some(gap) = self.gap {
+            style.push_str(&format!(" gap: {gap};"));
+        }
+        if self.wrap {
+            style.push_str(" flex-wrap: wrap;");
+        }
+
+        style
+    }
+}
+
+macro_rules! impl_flex_layout {
+    ($name:ident) => {
+        impl<T> $name<T> {
+            pub fn gap(mut self, gap: Length) -> Self {
+                self.flex.gap = Some(gap);
+                self
+            }
+
+            pub fn align(mut self, align: Align) -> Self {
+                self.flex.align = Some(align);
+                self
+            }
+
+            pub fn justify(mut self, justify: Justify) -> Self {
+                self.flex.justify = Some(justify);
+                self
+            }
+
+            pub fn wrap(mut self) -> Self {
+                self.flex.wrap = true;
+                self
+            }
+        }
+
+        impl<T: ComponentList> Component for $name<T> {
+            fn render_to(&self, cx: &mut RenderContext) {
+                cx.element(stringify!($name), "div", |cx| {
+                    cx.attribute("style", &self.flex.style());
+                    self.children.render_components_to(cx);
+                });
+            }
+
+            fn export(&self, nodes: &mut Vec<Node>) -> Result<(), ExportError> {
+                let mut children = Vec::new();
+                self.children.export_components(&mut children)?;
+                nodes.push(Node::$name {
+                    children,
+                    gap: self.flex.gap,
+                    align: self.flex.align,
+                    justify: self.flex.justify,
+                    wrap: self.flex.wrap,
+                });
+                Ok(())
+            }
+
+            fn tag(&self) -> Option<&'static str> {
+                Some("div")
+            }
+
+            fn children(&self) -> Vec<&dyn Component> {
+                self.children.child_components()
+            }
+        }
+    };
+}
+
+pub struct HList<T> {
+    children: T,
+    flex: Flex,
+}
+
+impl<T> HList<T> {
+    pub fn new(children: T) -> Self {
+        Self {
+            children,
+            flex: Flex {
+                column: false,
+                gap: None,
+                align: Some(Align::Center),
+                justify: None,
+                wrap: false,
+            },
+        }
+    }
+}
+
+impl_flex_layout!(HList);
+
+pub struct VStack<T> {
+    children: T,
+    flex: Flex,
+}
+
+impl<T> VStack<T> {
+    pub fn new(children: T) -> Self {
+        Self {
+            children,
+            flex: Flex {
+                column: true,
+                gap: None,
+                align: None,
+                justify: None,
+                wrap: false,
+            },
+        }
+    }
+}
+
+impl_flex_layout!(VStack);
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Columns {
+    Repeat(u32),
+    AutoFill(Length),
+    Template(Cow<'static, str>),
+}
+
+impl fmt::Display for Columns {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Columns::Repeat(count) => write!(f, "repeat({count}, 1fr)"),
+            Columns::AutoFill(min) => write!(f, "repeat(auto-fill, minmax({min}, 1fr))"),
+            Columns::Template(template) => f.write_str(template),
+        }
+    }
+}
+
+pub struct Grid<T> {
+    children: T,
+    columns: Columns,
+    gap: Option<Length>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(children: T, columns: Columns) -> Self {
+        Self {
+            children,
+            columns,
+            gap: None,
+        }
+    }
+
+    pub fn gap(mut self, gap: Length) -> Self {
+        self.gap = Some(gap);
+        self
+    }
+
+    fn style(&self) -> String {
+        let mut style = format!("display: grid; grid-template-columns: {};", self.columns);
+
+        if let Some(gap) = self.gap {
+            style.push_str(&format!(" gap: {gap};"));
+        }
+
+        style
+    }
+}
+
+impl<T: ComponentList> Component for Grid<T> {
+    fn render_to(&self, cx: &mut RenderContext) {
+        cx.element("Grid", "div", |cx| {
+            cx.attribute("style", &self.style());
+            self.children.render_components_to(cx);
+        });
+    }
+
+    fn export(&self, nodes: &mut Vec<Node>) -> Result<(), ExportError> {
+        let mut children = Vec::new();
+        self.children.export_components(&mut children)?;
+        nodes.push(Node::Grid {
+            children,
+            columns: self.columns.clone(),
+            gap: self.gap,
+        });
+        Ok(())
+    }
+
+    fn tag(&self) -> Option<&'static str> {
+        Some("div")
+    }
+
+    fn children(&self) -> Vec<&dyn Component> {
+        self.children.child_components()
+    }
+}
+
+pub struct MissingHeader;
+
+pub struct MissingMain;
+
+#[diagnostic::on_unimplemented(
+    message = "`{Self}`: a required layout slot has not been filled",
+    label = "fill this slot with a component before calling `into_page`"
+)]
+pub trait Slot: Component + 'static {}
+
+impl<T: Component + 'static> Slot for T {}
+
+pub struct Layout<Hr, M, F> {
+    head: Head,
+    header: Hr,
+    main: M,
+    footer: Option<F>,
+}
+
+impl Layout<MissingHeader, MissingMain, ()> {
+    pub fn new() -> Self {
+        Self {
+            head: Head::new(),
+            header: MissingHeader,
+            main: MissingMain,
+            footer: None,
+        }
+    }
+}
+
+impl Default for Layout<MissingHeader, MissingMain, ()> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Hr, M, F> Layout<Hr, M, F> {
+    pub fn head(mut self, head: Head) -> Self {
+        self.head = head;
+        self
+    }
+
+    pub fn header<T>(self, header: T) -> Layout<T, M, F> {
+        Layout {
+            head: self.head,
+            header,
+            main: self.main,
+            footer: self.footer,
+        }
+    }
+
+    pub fn main<T>(self, main: T) -> Layout<Hr, T, F> {
+        Layout {
+            head: self.head,
+            header: self.header,
+            main,
+            footer: self.footer,
+        }
+    }
+
+    pub fn footer<T>(self, footer: T) -> Layout<Hr, M, T> {
+        Layout {
+            head: self.head,
+            header: self.header,
+            main: self.main,
+            footer: Some(footer),
+        }
+    }
+}
+
+#[derive(ComponentList)]
+pub struct LayoutBody<Hr, M, F> {
+    header: Header<Hr>,
+    main: Main<M>,
+    footer: Option<Footer<F>>,
+}
+
+impl<Hr, M, F> Layout<Hr, M, F> {
+    pub fn into_page(self) -> Page<LayoutBody<Hr, M, F>>
+    where
+        Hr: Slot,
+        M: Slot,
+        F: Component + 'static,
+    {
+        Page::new(LayoutBody {
+            header: Header(self.header),
+            main: Main(self.main),
+            footer: self.footer.map(Footer),
+        })
+        .head(self.head)
+        .require_single_main()
+    }
+}
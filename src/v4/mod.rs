@@ -1,4 +1,292 @@
-mod components;
+pub mod ast;
+pub mod cache;
+pub mod components;
+pub mod content;
+pub mod context;
+pub mod etag;
+pub mod forms;
 mod handlers;
+pub mod head;
+pub mod layout;
+mod nesting;
+pub mod pretty;
+pub mod render;
+pub mod style;
+pub mod template;
+pub mod toc;
 
 pub use handlers::router;
+
+#[doc(hidden)]
+pub use impl_iter_tuple_kind_of_macros::html as __html;
+
+#[macro_export]
+macro_rules! html {
+    ($($tokens:tt)*) => {
+        $crate::v4::__html!($crate::v4::components; $($tokens)*)
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use axum::{http::StatusCode, response::IntoResponse};
+
+    use super::{
+        ast, cache::Cached, components::*, content::Phrasing, render::HtmlRenderer,
+        toc::TableOfContents,
+    };
+
+    #[test]
+    fn html_macro_matches_components() {
+        let name = "Ferris";
+        let expanded = crate::html! {
+            <header>
+                <img src="/public/logo.svg" width=50 height=50 />
+                <h2>"Hello " <strong>{name}</strong> <br/></h2>
+            </header>
+            <ul><li>"one"</li><li>"two"</li></ul>
+        };
+        let manual = (
+            Header((
+                Img {
+                    src: "/public/logo.svg",
+                    width: 50,
+                    height: 50,
+                },
+                H2(("Hello ", Strong(name), Br)),
+            )),
+            Ul(("one", "two")),
+        );
+
+        assert_eq!(expanded.render(), manual.render());
+    }
+
+    #[test]
+    fn html_macro_nests_long_child_lists() {
+        let paragraph = crate::html! {
+            <p>"a" "b" "c" "d" "e" "f" "g" "h" "i" "j" "k" "l" "m"</p>
+        };
+        let list = crate::html! {
+            <ul>
+                <li>"1"</li><li>"2"</li><li>"3"</li><li>"4"</li><li>"5"</li>
+                <li>"6"</li><li>"7"</li><li>"8"</li><li>"9"</li><li>"10"</li>
+                <li>"11"</li><li>"12"</li><li>"13"</li>
+            </ul>
+        };
+
+        assert_eq!(paragraph.render(), "<p>abcdefghijklm</p>");
+        assert_eq!(list.render().matches("<li>").count(), 13);
+    }
+
+    #[derive(Component)]
+    #[component(template = "<article><h3>{title}</h3>{{{count}}}<ul>{tags}</ul></article>")]
+    struct Card {
+        title: String,
+        count: u32,
+        #[component(list)]
+        tags: [Strong<&'static str>; 2],
+    }
+
+    #[test]
+    fn derive_component_from_template() {
+        let card = Card {
+            title: "Tuples & <Iterators>".to_string(),
+            count: 2,
+            tags: [Strong("rust"), Strong("talk")],
+        };
+
+        assert_eq!(
+            card.render(),
+            "<article><h3 id=\"tuples-iterators\">Tuples &amp; &lt;Iterators&gt;</h3>{2}<ul><strong>rust</strong><strong>talk</strong></ul></article>"
+        );
+    }
+
+    #[derive(ComponentList)]
+    struct Sections {
+        intro: P<&'static str>,
+        #[component(skip)]
+        _draft: bool,
+        outro: H3<&'static str>,
+    }
+
+    #[test]
+    fn derive_component_list_in_declaration_order() {
+        let sections = Sections {
+            intro: P("intro"),
+            _draft: true,
+            outro: H3("outro"),
+        };
+
+        assert_eq!(sections.iter_components().count(), 2);
+        assert_eq!(
+            sections.render_components(),
+            "<p>intro</p><h3 id=\"outro\">outro</h3>"
+        );
+    }
+
+    #[derive(Component)]
+    #[component(phrasing, template = "<span class=\"badge\">{label}</span>")]
+    struct Badge {
+        label: &'static str,
+    }
+
+    #[test]
+    fn derived_components_have_content_categories() {
+        let card = Card {
+            title: "Tuples".to_string(),
+            count: 1,
+            tags: [Strong("rust"), Strong("talk")],
+        };
+        let html = (
+            P(("New ", Badge { label: "talk" })),
+            Ul([card]),
+            H2(Strong("boxed").to_phrasing()),
+        )
+            .render();
+
+        assert!(html.starts_with("<p>New <span class=\"badge\">talk</span></p><ul><li><article>"));
+        assert!(html.ends_with("<h2 id=\"boxed\"><strong>boxed</strong></h2>"));
+    }
+
+    #[derive(Component, ComponentList)]
+    #[component(template = "<section>{intro}<ul>{tags}</ul></section>")]
+    struct Talk {
+        intro: P<&'static str>,
+        #[component(list)]
+        tags: [Strong<&'static str>; 2],
+        #[component(skip)]
+        _draft: bool,
+    }
+
+    #[test]
+    fn derive_component_and_component_list_together() {
+        let talk = Talk {
+            intro: P("intro"),
+            tags: [Strong("rust"), Strong("talk")],
+            _draft: true,
+        };
+
+        assert_eq!(
+            talk.render(),
+            "<section><p>intro</p><ul><strong>rust</strong><strong>talk</strong></ul></section>"
+        );
+        assert_eq!(
+            talk.render_components(),
+            "<p>intro</p><strong>rust</strong><strong>talk</strong>"
+        );
+    }
+
+    #[test]
+    fn walk_depth_first() {
+        let page = (
+            Header(crate::html! {
+                <img src="/public/logo.svg" width=50 height=50 />
+                <h2>"Kansai Rust"</h2>
+            }),
+            Main(
+                [
+                    H1("Tuples").to_dyn(),
+                    P(("Use a ", Strong("tuple"))).to_dyn(),
+                    Ul([H3("nested")]).to_dyn(),
+                ]
+                .to_dyn(),
+            ),
+        );
+        let tags = walk(&page)
+            .filter_map(|component| component.tag())
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            tags,
+            ["header", "img", "h2", "main", "h1", "p", "strong", "ul", "h3"]
+        );
+    }
+
+    #[derive(Component)]
+    enum Block {
+        Heading(H2<&'static str>),
+        Card { card: Card },
+        Empty,
+    }
+
+    #[test]
+    fn walk_derived_components() {
+        let blocks = [
+            Block::Heading(H2("Agenda")),
+            Block::Card {
+                card: Card {
+                    title: "Tuples".to_string(),
+                    count: 1,
+                    tags: [Strong("rust"), Strong("talk")],
+                },
+            },
+            Block::Empty,
+        ];
+        let tags = walk(&blocks)
+            .filter_map(|component| component.tag())
+            .collect::<Vec<_>>();
+
+        assert_eq!(tags, ["h2", "strong", "strong"]);
+        assert_eq!(blocks[1].children().len(), 4);
+        assert_eq!(blocks[2].tag(), None);
+    }
+
+    #[test]
+    fn derived_enums_forward_export() {
+        assert_eq!(
+            ast::export(&[Block::Heading(H2("Agenda")), Block::Empty]).unwrap(),
+            ast::export(&H2("Agenda")).unwrap()
+        );
+    }
+
+    #[derive(Component)]
+    #[component(template = "<main><h2>{title}</h2>{body}</main>")]
+    struct Shell {
+        title: &'static str,
+        body: P<&'static str>,
+    }
+
+    fn shell(title: &'static str) -> Shell {
+        Shell {
+            title,
+            body: P("15 minutes"),
+        }
+    }
+
+    #[test]
+    fn template_tags_are_tracked() {
+        let html = Page::new((TableOfContents, shell("Agenda"))).render_as::<HtmlRenderer>();
+        let single = Page::new((shell("Agenda"),)).require_single_main();
+        let twice = Page::new((shell("Agenda"), Main("b"))).require_single_main();
+
+        assert!(html.contains(
+            "<a href=\"#agenda\">Agenda</a></li></ul></nav><main><h2 id=\"agenda\">Agenda</h2>"
+        ));
+        assert_eq!(single.into_response().status(), StatusCode::OK);
+        assert_eq!(
+            twice.into_response().status(),
+            StatusCode::INTERNAL_SERVER_ERROR
+        );
+    }
+
+    #[test]
+    fn require_single_main() {
+        let missing = Page::new((Header("Kansai Rust"),)).require_single_main();
+        let twice = Page::new((Main("one"), Main("two"))).require_single_main();
+
+        assert_eq!(
+            missing.into_response().status(),
+            StatusCode::INTERNAL_SERVER_ERROR
+        );
+        assert_eq!(
+            twice.into_response().status(),
+            StatusCode::INTERNAL_SERVER_ERROR
+        );
+
+        for _ in 0..2 {
+            let cached = Page::new((Cached::new("test/single-main", || Main("cached")),))
+                .require_single_main();
+            assert_eq!(cached.into_response().status(), StatusCode::OK);
+        }
+    }
+}
@@ -0,0 +1,113 @@
+const PHRASING: &[&str] = &[
+    "strong", "em", "code", "span", "small", "mark", "br", "img", "button",
+];
+
+pub(super) struct Nesting {
+    strict: bool,
+    path: Vec<(&'static str, &'static str)>,
+}
+
+fn allowed(ancestor: &str, tag: &str) -> bool {
+    match ancestor {
+        "p" | "h1" | "h2" | "h3" | "strong" | "em" | "code" | "span" | "small" | "mark"
+        | "button" => PHRASING.contains(&tag),
+        "header" | "footer" => !matches!(tag, "header" | "footer" | "main"),
+        "nav" | "section" | "article" | "aside" => tag != "main",
+        "form" => tag != "form",
+        _ => true,
+    }
+}
+
+impl Nesting {
+    pub(super) fn new(strict: bool) -> Self {
+        Self {
+            strict,
+            path: Vec::new(),
+        }
+    }
+
+    pub(super) fn enter(&mut self, component: &'static str, tag: &'static str) {
+        if let Some((_, ancestor)) = self
+            .path
+            .iter()
+            .rev()
+            .find(|(_, ancestor)| !allowed(ancestor, tag))
+        {
+            let components = self
+                .path
+                .iter()
+                .map(|(component, _)| *component)
+                .chain([component])
+                .collect::<Vec<_>>()
+                .join(" > ");
+            let message = format!(
+                "invalid HTML nesting: <{tag}> is not allowed inside <{ancestor}> at {components}"
+            );
+
+            if self.strict {
+                panic!("{message}");
+            }
+            tracing::error!("{message}");
+        }
+
+        self.path.push((component, tag));
+    }
+
+    pub(super) fn exit(&mut self) {
+        self.path.pop();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Nesting;
+    use crate::v4::{
+        components::{Component, Main, Section, Strong, Ul, P},
+        context::RenderContext,
+        render::{HtmlRenderer, Renderer},
+    };
+
+    fn render_strict(component: impl Component) -> String {
+        let mut renderer = HtmlRenderer::default();
+        let mut cx = RenderContext::new(&mut renderer);
+        cx.validate_nesting(true);
+        component.render_to(&mut cx);
+        renderer.finish()
+    }
+
+    #[test]
+    fn accepts_valid_nesting() {
+        render_strict(Main((P(Strong("tuple")), Ul([P("item")]))));
+    }
+
+    #[test]
+    #[should_panic(expected = "<ul> is not allowed inside <p> at Main > P > Ul")]
+    fn rejects_list_in_paragraph() {
+        let mut nesting = Nesting::new(true);
+        nesting.enter("Main", "main");
+        nesting.enter("P", "p");
+        nesting.enter("Ul", "ul");
+    }
+
+    #[test]
+    #[should_panic(expected = "<div> is not allowed inside <h1> at H1 > HList")]
+    fn rejects_layout_in_heading() {
+        let mut nesting = Nesting::new(true);
+        nesting.enter("H1", "h1");
+        nesting.enter("HList", "div");
+    }
+
+    #[test]
+    #[should_panic(expected = "<ul> is not allowed inside <strong> at Main > Strong > Ul")]
+    #[cfg(debug_assertions)]
+    fn rejects_list_in_inline_wrapper() {
+        render_strict(Main(Strong(Ul(["item"]))));
+    }
+
+    #[test]
+    #[should_panic(expected = "<main> is not allowed inside <section> at Section > Main")]
+    #[cfg(debug_assertions)]
+    fn rejects_main_in_section() {
+        render_strict(Section(Main("content")));
+    }
+}
@@ -0,0 +1,291 @@
+const BLOCK: &[&str] = &[
+    "html", "head", "body", "title", "meta", "link", "style", "script", "header", "nav", "main",
+    "section", "article", "aside", "footer", "div", "p", "h1", "h2", "h3", "h4", "h5", "h6", "ul",
+    "ol", "li", "form", "fieldset",
+];
+
+pub(super) const VOID: &[&str] = &["meta", "link", "img", "br", "hr", "input"];
+
+const RAW_TEXT: &[&str] = &["style", "script", "textarea", "pre"];
+
+enum Node<'a> {
+    Text(&'a str),
+    Raw(&'a str),
+    Other(&'a str),
+    Element {
+        name: String,
+        open: &'a str,
+        children: Vec<Node<'a>>,
+        close: &'a str,
+    },
+}
+
+impl Node<'_> {
+    fn is_block(&self) -> bool {
+        match self {
+            Node::Other(_) => true,
+            Node::Element { name, .. } => BLOCK.contains(&name.as_str()),
+            Node::Text(_) | Node::Raw(_) => false,
+        }
+    }
+}
+
+struct Open<'a> {
+    name: String,
+    open: &'a str,
+    children: Vec<Node<'a>>,
+}
+
+pub(super) fn tag_end(html: &str) -> usize {
+    let mut quote = None;
+
+    for (index, c) in html.char_indices() {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(q), c) if q == c => quote = None,
+            (None, '>') => return index + 1,
+            _ => {}
+        }
+    }
+
+    html.len()
+}
+
+pub(super) fn tag_name(tag: &str) -> String {
+    tag.trim_start_matches("</")
+        .trim_start_matches('<')
+        .split(|c: char| c.is_whitespace() || c == '/' || c == '>')
+        .next()
+        .unwrap_or_default()
+        .to_ascii_lowercase()
+}
+
+fn parse(html: &str) -> Vec<Node<'_>> {
+    let mut stack = vec![Open {
+        name: String::new(),
+        open: "",
+        children: Vec::new(),
+    }];
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        if rest.starts_with("<!--") {
+            let end = rest.find("-->").map_or(rest.len(), |end| end + 3);
+            push(&mut stack, Node::Other(&rest[..end]));
+            rest = &rest[end..];
+        } else if rest.starts_with("<!") {
+            let end = tag_end(rest);
+            push(&mut stack, Node::Other(&rest[..end]));
+            rest = &rest[end..];
+        } else if rest.starts_with("</") {
+            let end = tag_end(rest);
+            let name = tag_name(&rest[..end]);
+
+            match stack.iter().rposition(|open| open.name == name) {
+                Some(position) if position > 0 => {
+                    while stack.len() > position + 1 {
+                        close(&mut stack, "");
+                    }
+                    close(&mut stack, &rest[..end]);
+                }
+                _ => push(&mut stack, Node::Text(&rest[..end])),
+            }
+            rest = &rest[end..];
+        } else if rest.starts_with('<') && rest[1..].starts_with(|c: char| c.is_ascii_alphabetic())
+        {
+            let end = tag_end(rest);
+            let open = &rest[..end];
+            let name = tag_name(open);
+            rest = &rest[end..];
+
+            if VOID.contains(&name.as_str()) || open.ends_with("/>") {
+                push(
+                    &mut stack,
+                    Node::Element {
+                        name,
+                        open,
+                        children: Vec::new(),
+                        close: "",
+                    },
+                );
+            } else if RAW_TEXT.contains(&name.as_str()) {
+                let content_end = rest.find(&format!("</{name}")).unwrap_or(rest.len());
+                let close_end = content_end + tag_end(&rest[content_end..]);
+                push(
+                    &mut stack,
+                    Node::Element {
+                        name,
+                        open,
+                        children: vec![Node::Raw(&rest[..content_end])],
+                        close: &rest[content_end..close_end],
+                    },
+                );
+                rest = &rest[close_end..];
+            } else {
+                stack.push(Open {
+                    name,
+                    open,
+                    children: Vec::new(),
+                });
+            }
+        } else {
+            let first = rest.chars().next().map_or(1, char::len_utf8);
+            let end = rest[first..]
+                .find('<')
+                .map_or(rest.len(), |end| end + first);
+            push(&mut stack, Node::Text(&rest[..end]));
+            rest = &rest[end..];
+        }
+    }
+
+    while stack.len() > 1 {
+        close(&mut stack, "");
+    }
+
+    stack.pop().unwrap().children
+}
+
+fn push<'a>(stack: &mut [Open<'a>], node: Node<'a>) {
+    stack.last_mut().unwrap().children.push(node);
+}
+
+fn close<'a>(stack: &mut Vec<Open<'a>>, tag: &'a str) {
+    let Open {
+        name,
+        open,
+        children,
+    } = stack.pop().unwrap();
+
+    push(
+        stack,
+        Node::Element {
+            name,
+            open,
+            children,
+            close: tag,
+        },
+    );
+}
+
+fn inline(node: &Node, out: &mut String) {
+    match node {
+        Node::Text(text) => {
+            let mut last_was_space = out.ends_with(' ');
+            for c in text.chars() {
+                if c.is_whitespace() {
+                    if !last_was_space {
+                        out.push(' ');
+                    }
+                    last_was_space = true;
+                } else {
+                    out.push(c);
+                    last_was_space = false;
+                }
+            }
+        }
+        Node::Raw(raw) | Node::Other(raw) => out.push_str(raw),
+        Node::Element {
+            open,
+            children,
+            close,
+            ..
+        } => {
+            out.push_str(open);
+            for child in children {
+                inline(child, out);
+            }
+            out.push_str(close);
+        }
+    }
+}
+
+fn line(out: &mut String, depth: usize, content: &str) {
+    let content = content.trim();
+    if content.is_empty() {
+        return;
+    }
+
+    for _ in 0..depth {
+        out.push_str("  ");
+    }
+    out.push_str(content);
+    out.push('\n');
+}
+
+fn block(node: &Node, depth: usize, out: &mut String) {
+    match node {
+        Node::Element {
+            open,
+            children,
+            close,
+            ..
+        } if children.iter().any(Node::is_block) => {
+            line(out, depth, open);
+            nodes(children, depth + 1, out);
+            line(out, depth, close);
+        }
+        node => {
+            let mut content = String::new();
+            inline(node, &mut content);
+            line(out, depth, &content);
+        }
+    }
+}
+
+fn nodes(nodes: &[Node], depth: usize, out: &mut String) {
+    let mut run = String::new();
+
+    for node in nodes {
+        if node.is_block() {
+            line(out, depth, &run);
+            run.clear();
+            block(node, depth, out);
+        } else {
+            inline(node, &mut run);
+        }
+    }
+
+    line(out, depth, &run);
+}
+
+pub fn pretty(html: &str) -> String {
+    let mut out = String::new();
+    nodes(&parse(html), 0, &mut out);
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn indents_blocks_and_keeps_inline_content() {
+        let html =
+            "<!Doctype html><html lang=\"en\"><head><style>a > b { color: red; }</style></head>\
+            <body><main><h1>Title</h1><p>Hello <strong>tuple</strong>\n    world<br/></p>\
+            <ul><li>one</li><li><p>two</p></li></ul></main></body></html>";
+
+        assert_eq!(
+            pretty(html),
+            "<!Doctype html>
+<html lang=\"en\">
+  <head>
+    <style>a > b { color: red; }</style>
+  </head>
+  <body>
+    <main>
+      <h1>Title</h1>
+      <p>Hello <strong>tuple</strong> world<br/></p>
+      <ul>
+        <li>one</li>
+        <li>
+          <p>two</p>
+        </li>
+      </ul>
+    </main>
+  </body>
+</html>
+"
+        );
+    }
+}
@@ -0,0 +1,502 @@
+use super::{
+    components::TrustedHtml,
+    pretty::{tag_end, tag_name, VOID},
+};
+
+pub trait Renderer {
+    fn start_element(&mut self, tag: &str);
+
+    fn attribute(&mut self, name: &str, value: &str);
+
+    fn end_element(&mut self, tag: &str);
+
+    fn text(&mut self, text: &str);
+
+    fn raw(&mut self, html: &TrustedHtml) {
+        raw_events(html.as_str(), self);
+    }
+
+    fn finish(self) -> String
+    where
+        Self: Sized;
+}
+
+fn attributes(tag: &str) -> Vec<(String, String)> {
+    let mut rest = tag
+        .trim_start_matches('<')
+        .trim_end_matches('>')
+        .trim_end_matches('/')
+        .trim_start_matches(|c: char| !c.is_whitespace());
+    let mut attributes = Vec::new();
+
+    loop {
+        rest = rest.trim_start();
+        if rest.is_empty() {
+            break;
+        }
+
+        let name_end = rest
+            .find(|c: char| c == '=' || c.is_whitespace())
+            .unwrap_or(rest.len());
+        let name = &rest[..name_end];
+        rest = rest[name_end..].trim_start();
+
+        let mut value = "";
+        if let Some(after) = rest.strip_prefix('=') {
+            let after = after.trim_start();
+            (value, rest) = match after.chars().next() {
+                Some(quote @ ('"' | '\'')) => {
+                    let end = after[1..].find(quote).map_or(after.len(), |end| end + 1);
+                    (&after[1..end], after.get(end + 1..).unwrap_or_default())
+                }
+                _ => {
+                    let end = after.find(char::is_whitespace).unwrap_or(after.len());
+                    (&after[..end], &after[end..])
+                }
+            };
+        }
+
+        if !name.is_empty() {
+            attributes.push((
+                name.to_ascii_lowercase(),
+                html_escape::decode_html_entities(value).to_string(),
+            ));
+        }
+    }
+
+    attributes
+}
+
+fn raw_events<R: Renderer + ?Sized>(html: &str, renderer: &mut R) {
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        if rest.starts_with("<!--") {
+            let end = rest.find("-->").map_or(rest.len(), |end| end + 3);
+            rest = &rest[end..];
+        } else if rest.starts_with("<!") {
+            rest = &rest[tag_end(rest)..];
+        } else if rest.starts_with("</") {
+            let end = tag_end(rest);
+            renderer.end_element(&tag_name(&rest[..end]));
+            rest = &rest[end..];
+        } else if rest.starts_with('<') && rest[1..].starts_with(|c: char| c.is_ascii_alphabetic())
+        {
+            let end = tag_end(rest);
+            let tag = &rest[..end];
+            let name = tag_name(tag);
+            rest = &rest[end..];
+
+            if name == "style" || name == "script" {
+                let close = rest.find(&format!("</{name}")).unwrap_or(rest.len());
+                rest = &rest[close + tag_end(&rest[close..])..];
+                continue;
+            }
+
+            renderer.start_element(&name);
+            for (name, value) in attributes(tag) {
+                renderer.attribute(&name, &value);
+            }
+            if VOID.contains(&name.as_str()) || tag.ends_with("/>") {
+                renderer.end_element(&name);
+            }
+        } else {
+            let first = rest.chars().next().map_or(1, char::len_utf8);
+            let end = rest[first..]
+                .find('<')
+                .map_or(rest.len(), |end| end + first);
+            renderer.text(&html_escape::decode_html_entities(&rest[..end]));
+            rest = &rest[end..];
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct HtmlRenderer {
+    html: String,
+    open: bool,
+}
+
+impl HtmlRenderer {
+    fn close_start(&mut self) {
+        if self.open {
+            self.html.push('>');
+            self.open = false;
+        }
+    }
+}
+
+impl Renderer for HtmlRenderer {
+    fn start_element(&mut self, tag: &str) {
+        self.close_start();
+        self.html.push('<');
+        self.html.push_str(tag);
+        self.open = true;
+    }
+
+    fn attribute(&mut self, name: &str, value: &str) {
+        let value = html_escape::encode_double_quoted_attribute(value);
+        self.html.push_str(&format!(" {name}=\"{value}\""));
+    }
+
+    fn end_element(&mut self, tag: &str) {
+        if self.open && VOID.contains(&tag) {
+            self.html.push_str("/>");
+            self.open = false;
+            return;
+        }
+
+        self.close_start();
+        self.html.push_str(&format!("</{tag}>"));
+    }
+
+    fn text(&mut self, text: &str) {
+        self.close_start();
+        self.html.push_str(&html_escape::encode_text(text));
+    }
+
+    fn raw(&mut self, html: &TrustedHtml) {
+        self.close_start();
+        self.html.push_str(html.as_str());
+    }
+
+    fn finish(mut self) -> String {
+        self.close_start();
+        self.html
+    }
+}
+
+const BLOCK: &[&str] = &[
+    "header", "nav", "main", "section", "article", "aside", "footer", "div", "p", "form",
+];
+
+#[derive(Default)]
+struct Writer {
+    out: String,
+    lists: usize,
+    links: Vec<String>,
+}
+
+impl Writer {
+    fn trim(&mut self) {
+        let len = self.out.trim_end_matches(' ').len();
+        self.out.truncate(len);
+    }
+
+    fn block(&mut self) {
+        if self.lists > 0 {
+            return;
+        }
+
+        self.trim();
+        if !self.out.is_empty() {
+            while !self.out.ends_with("\n\n") {
+                self.out.push('\n');
+            }
+        }
+    }
+
+    fn start_list(&mut self) {
+        self.block();
+        self.lists += 1;
+    }
+
+    fn end_list(&mut self) {
+        self.lists = self.lists.saturating_sub(1);
+        self.block();
+    }
+
+    fn item(&mut self) {
+        self.trim();
+        if !self.out.is_empty() && !self.out.ends_with('\n') {
+            self.out.push('\n');
+        }
+        for _ in 1..self.lists {
+            self.out.push_str("  ");
+        }
+        self.out.push_str("- ");
+    }
+
+    fn text(&mut self, text: &str, escape: impl Fn(char, &mut String)) {
+        for c in text.chars() {
+            if !c.is_ascii_whitespace() {
+                escape(c, &mut self.out);
+            } else if !self.out.is_empty() && !self.out.ends_with([' ', '\n']) {
+                self.out.push(' ');
+            }
+        }
+    }
+
+    fn finish(mut self) -> String {
+        let len = self.out.trim_end().len();
+        self.out.truncate(len);
+        self.out.push('\n');
+        self.out
+    }
+}
+
+#[derive(Default)]
+pub struct MarkdownRenderer {
+    writer: Writer,
+    code: usize,
+}
+
+impl Renderer for MarkdownRenderer {
+    fn start_element(&mut self, tag: &str) {
+        let writer = &mut self.writer;
+
+        match tag {
+            "h1" | "h2" | "h3" => {
+                writer.block();
+                writer.out.push_str(match tag {
+                    "h1" => "# ",
+                    "h2" => "## ",
+                    _ => "### ",
+                });
+            }
+            "ul" => writer.start_list(),
+            "li" => writer.item(),
+            "strong" => writer.out.push_str("**"),
+            "em" => writer.out.push('_'),
+            "code" => {
+                writer.out.push('`');
+                self.code += 1;
+            }
+            "br" => writer.out.push_str("  \n"),
+            "a" => {
+                writer.out.push('[');
+                writer.links.push(String::new());
+            }
+            tag if BLOCK.contains(&tag) => writer.block(),
+            _ => {}
+        }
+    }
+
+    fn attribute(&mut self, name: &str, value: &str) {
+        let writer = &mut self.writer;
+
+        match name {
+            "src" => writer.out.push_str(&format!("![]({value})")),
+            "href" => {
+                if let Some(link) = writer.links.last_mut() {
+                    *link = value.to_string();
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn end_element(&mut self, tag: &str) {
+        let writer = &mut self.writer;
+
+        match tag {
+            "ul" => writer.end_list(),
+            "strong" => writer.out.push_str("**"),
+            "em" => writer.out.push('_'),
+            "code" => {
+                writer.out.push('`');
+                self.code = self.code.saturating_sub(1);
+            }
+            "a" => {
+                let link = writer.links.pop().unwrap_or_default();
+                writer.out.push_str(&format!("]({link})"));
+            }
+            tag if tag.starts_with('h') || BLOCK.contains(&tag) => writer.block(),
+            _ => {}
+        }
+    }
+
+    fn text(&mut self, text: &str) {
+        if self.code > 0 {
+            self.writer.text(text, |c, out| out.push(c));
+        } else {
+            self.writer.text(text, |c, out| {
+                if matches!(c, '\\' | '`' | '*' | '_' | '[' | ']' | '<') {
+                    out.push('\\');
+                }
+                out.push(c);
+            });
+        }
+    }
+
+    fn finish(self) -> String {
+        self.writer.finish()
+    }
+}
+
+#[derive(Default)]
+pub struct TextRenderer {
+    writer: Writer,
+}
+
+impl Renderer for TextRenderer {
+    fn start_element(&mut self, tag: &str) {
+        let writer = &mut self.writer;
+
+        match tag {
+            "ul" => writer.start_list(),
+            "li" => writer.item(),
+            "br" => writer.out.push('\n'),
+            "a" => writer.links.push(String::new()),
+            tag if tag.starts_with('h') || BLOCK.contains(&tag) => writer.block(),
+            _ => {}
+        }
+    }
+
+    fn attribute(&mut self, name: &str, value: &str) {
+        if let ("href", Some(link)) = (name, self.writer.links.last_mut()) {
+            *link = value.to_string();
+        }
+    }
+
+    fn end_element(&mut self, tag: &str) {
+        let writer = &mut self.writer;
+
+        match tag {
+            "h1" | "h2" => {
+                writer.trim();
+                let line = writer.out.rsplit('\n').next().unwrap_or_default();
+                let underline = if tag == "h1" { "=" } else { "-" };
+                let underline = underline.repeat(line.chars().count());
+                writer.out.push('\n');
+                writer.out.push_str(&underline);
+                writer.block();
+            }
+            "ul" => writer.end_list(),
+            "a" => {
+                let link = writer.links.pop().unwrap_or_default();
+                writer.out.push_str(&format!(" ({link})"));
+            }
+            tag if tag.starts_with('h') || BLOCK.contains(&tag) => writer.block(),
+            _ => {}
+        }
+    }
+
+    fn text(&mut self, text: &str) {
+        self.writer.text(text, |c, out| out.push(c));
+    }
+
+    fn finish(self) -> String {
+        self.writer.finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::v4::{components::*, forms::*, layout::*};
+
+    fn page() -> impl Component {
+        (
+            H1("Tuples"),
+            P((
+                "Use a ",
+                Strong("tuple"),
+                " instead of ",
+                Code("Vec<Box<_>>"),
+            )),
+            Ul(["15 minutes chat", "30 minutes talk"]),
+            Raw(TrustedHtml::from_static(
+                "<p>Held at <em>Room&nbsp;102</em><br/>in Kyoto</p>",
+            )),
+        )
+    }
+
+    #[test]
+    fn html_matches_render() {
+        let page = page();
+        assert_eq!(page.render_as::<HtmlRenderer>(), page.render());
+
+        let widgets = (
+            HList::new((
+                Img {
+                    src: "/public/logo.svg",
+                    width: 50,
+                    height: 50,
+                },
+                Strong("Kansai Rust"),
+            ))
+            .gap(Length::Px(12)),
+            VStack::new(["a", "b"]),
+            Grid::new(["c"], Columns::Template("1fr \"2fr\"".into())),
+            Form {
+                action: "/v4/rsvp".into(),
+                method: Method::Post,
+                children: (
+                    Input {
+                        name: "name".into(),
+                        label: "Name".into(),
+                        kind: InputKind::Text,
+                        value: "\"Ferris\"".to_string(),
+                        error: Some("Too crabby".to_string()),
+                    },
+                    Select {
+                        name: "attendance".into(),
+                        label: "Attendance".into(),
+                        options: vec![("online".into(), "Online".into())],
+                        selected: "online".to_string(),
+                        error: None,
+                    },
+                    Button("Send"),
+                ),
+            },
+        );
+
+        assert_eq!(widgets.render_as::<HtmlRenderer>(), widgets.render());
+        assert_eq!(
+            widgets.render(),
+            "<div style=\"display: flex; align-items: center; gap: 12px;\">\
+            <img src=\"/public/logo.svg\" width=\"50\" height=\"50\"/><strong>Kansai Rust</strong></div>\
+            <div style=\"display: flex; flex-direction: column;\">ab</div>\
+            <div style=\"display: grid; grid-template-columns: 1fr &quot;2fr&quot;;\">c</div>\
+            <form action=\"/v4/rsvp\" method=\"post\">\
+            <div class=\"field\"><label>Name<input type=\"text\" name=\"name\" value=\"&quot;Ferris&quot;\"/></label>\
+            <p class=\"field-error\">Too crabby</p></div>\
+            <div class=\"field\"><label>Attendance<select name=\"attendance\">\
+            <option value=\"online\" selected=\"\">Online</option></select></label></div>\
+            <button type=\"submit\">Send</button></form>"
+        );
+    }
+
+    #[test]
+    fn markdown() {
+        assert_eq!(
+            page().render_as::<MarkdownRenderer>(),
+            "# Tuples
+
+Use a **tuple** instead of `Vec<Box<_>>`
+
+- 15 minutes chat
+- 30 minutes talk
+
+Held at _Room\u{a0}102_\x20\x20
+in Kyoto
+"
+        );
+    }
+
+    #[test]
+    fn stray_closing_list_tag() {
+        let component = (Raw(TrustedHtml::from_static("</ul>")), "after");
+
+        assert_eq!(component.render_as::<MarkdownRenderer>(), "after\n");
+    }
+
+    #[test]
+    fn plain_text() {
+        assert_eq!(
+            page().render_as::<TextRenderer>(),
+            "Tuples
+======
+
+Use a tuple instead of Vec<Box<_>>
+
+- 15 minutes chat
+- 30 minutes talk
+
+Held at Room\u{a0}102
+in Kyoto
+"
+        );
+    }
+}
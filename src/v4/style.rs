@@ -0,0 +1,75 @@
+use std::{
+    any::{type_name, TypeId},
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+};
+
+use super::{cache::Event, context::RenderContext};
+
+#[derive(Default)]
+pub(super) struct Styles(Vec<(TypeId, String)>);
+
+impl Styles {
+    fn insert(&mut self, id: TypeId, css: &str) {
+        if !self.0.iter().any(|(existing, _)| *existing == id) {
+            self.0.push((id, css.to_string()));
+        }
+    }
+
+    pub(super) fn render(&self) -> String {
+        if self.0.is_empty() {
+            return String::new();
+        }
+
+        let css: Vec<_> = self.0.iter().map(|(_, css)| css.as_str()).collect();
+        format!("<style>{}</style>", css.join("\n"))
+    }
+}
+
+pub fn scope_class<T: ?Sized + 'static>() -> String {
+    let mut hasher = DefaultHasher::new();
+    type_name::<T>().hash(&mut hasher);
+    format!("c-{:08x}", hasher.finish() as u32)
+}
+
+pub fn scoped_style<T: ?Sized + 'static>(cx: &mut RenderContext, css: &str) -> String {
+    let class = scope_class::<T>();
+    register(
+        cx,
+        TypeId::of::<T>(),
+        css.replace('&', &format!(".{class}")),
+    );
+    class
+}
+
+pub(super) fn register(cx: &mut RenderContext, id: TypeId, css: String) {
+    cx.styles.insert(id, &css);
+    cx.record(Event::Style(id, css));
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::v4::render::HtmlRenderer;
+
+    struct Card;
+    struct Badge;
+
+    #[test]
+    fn collects_each_type_once() {
+        let mut renderer = HtmlRenderer::default();
+        let mut cx = RenderContext::new(&mut renderer);
+
+        let card = scoped_style::<Card>(&mut cx, "& { padding: 1rem; } & h3 { margin: 0; }");
+        scoped_style::<Card>(&mut cx, "& { padding: 1rem; } & h3 { margin: 0; }");
+        let badge = scoped_style::<Badge>(&mut cx, "& { color: red; }");
+
+        assert_ne!(card, badge);
+        assert_eq!(
+            cx.styles.render(),
+            format!(
+                "<style>.{card} {{ padding: 1rem; }} .{card} h3 {{ margin: 0; }}\n.{badge} {{ color: red; }}</style>"
+            )
+        );
+    }
+}
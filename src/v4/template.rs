@@ -0,0 +1,252 @@
+use std::{fmt, path::Path, sync::Arc};
+
+use super::{
+    components::{Component, TrustedHtml},
+    context::RenderContext,
+};
+
+#[derive(Debug)]
+pub enum TemplateError {
+    Io(String, std::io::Error),
+    Unclosed {
+        template: String,
+        line: usize,
+    },
+    UnknownSlot {
+        template: String,
+        slot: String,
+        line: usize,
+        provided: Vec<&'static str>,
+    },
+    MissingSlot {
+        template: String,
+        slot: &'static str,
+    },
+}
+
+impl fmt::Display for TemplateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TemplateError::Io(template, err) => {
+                write!(f, "failed to read template `{template}`: {err}")
+            }
+            TemplateError::Unclosed { template, line } => {
+                write!(
+                    f,
+                    "template `{template}` has an unclosed `{{{{` on line {line}"
+                )
+            }
+            TemplateError::UnknownSlot {
+                template,
+                slot,
+                line,
+                provided,
+            } => write!(
+                f,
+                "template `{template}` references slot `{slot}` on line {line}, \
+                 but only [{}] are provided",
+                provided.join(", ")
+            ),
+            TemplateError::MissingSlot { template, slot } => {
+                write!(f, "template `{template}` slot `{slot}` was not filled")
+            }
+        }
+    }
+}
+
+impl std::error::Error for TemplateError {}
+
+enum Segment {
+    Html(TrustedHtml),
+    Slot(&'static str),
+}
+
+struct Source {
+    name: String,
+    segments: Vec<Segment>,
+}
+
+#[derive(Clone)]
+pub struct TemplateSource(Arc<Source>);
+
+impl TemplateSource {
+    pub fn parse(name: &str, source: &str, slots: &[&'static str]) -> Result<Self, TemplateError> {
+        let mut segments = Vec::new();
+        let mut rest = source;
+
+        while let Some(start) = rest.find("{{") {
+            let line = source[..source.len() - rest.len() + start]
+                .matches('\n')
+                .count()
+                + 1;
+            segments.push(Segment::Html(TrustedHtml::from_template(
+                rest[..start].to_string(),
+            )));
+
+            let Some(end) = rest[start..].find("}}") else {
+                return Err(TemplateError::Unclosed {
+                    template: name.to_string(),
+                    line,
+                });
+            };
+
+            let slot = rest[start + 2..start + end].trim();
+            let Some(slot) = slots.iter().find(|provided| **provided == slot) else {
+                return Err(TemplateError::UnknownSlot {
+                    template: name.to_string(),
+                    slot: slot.to_string(),
+                    line,
+                    provided: slots.to_vec(),
+                });
+            };
+            segments.push(Segment::Slot(slot));
+
+            rest = &rest[start + end + 2..];
+        }
+
+        segments.push(Segment::Html(TrustedHtml::from_template(rest.to_string())));
+
+        Ok(Self(Arc::new(Source {
+            name: name.to_string(),
+            segments,
+        })))
+    }
+
+    pub fn load(path: impl AsRef<Path>, slots: &[&'static str]) -> Result<Self, TemplateError> {
+        let path = path.as_ref();
+        let name = path.display().to_string();
+        let source =
+            std::fs::read_to_string(path).map_err(|err| TemplateError::Io(name.clone(), err))?;
+        Self::parse(&name, &source, slots)
+    }
+
+    pub fn template(&self) -> Template {
+        Template {
+            source: self.clone(),
+            slots: Vec::new(),
+        }
+    }
+}
+
+pub struct Template {
+    source: TemplateSource,
+    slots: Vec<(&'static str, Box<dyn Component>)>,
+}
+
+impl Template {
+    pub fn slot(mut self, name: &'static str, component: impl Component + 'static) -> Self {
+        self.slots.push((name, component.to_dyn()));
+        self
+    }
+
+    pub fn build(self) -> Result<Self, TemplateError> {
+        match self.missing_slot() {
+            Some(slot) => Err(TemplateError::MissingSlot {
+                template: self.source.0.name.clone(),
+                slot,
+            }),
+            None => Ok(self),
+        }
+    }
+
+    fn missing_slot(&self) -> Option<&'static str> {
+        self.source
+            .0
+            .segments
+            .iter()
+            .find_map(|segment| match segment {
+                Segment::Slot(name) if !self.slots.iter().any(|(slot, _)| slot == name) => {
+                    Some(*name)
+                }
+                _ => None,
+            })
+    }
+}
+
+impl Component for Template {
+    fn render_to(&self, cx: &mut RenderContext) {
+        if let Some(slot) = self.missing_slot() {
+            let template = &self.source.0.name;
+            debug_assert!(false, "template `{template}` slot `{slot}` was not filled");
+            tracing::error!("template `{template}` slot `{slot}` was not filled");
+        }
+
+        for segment in self.source.0.segments.iter() {
+            match segment {
+                Segment::Html(html) => cx.raw(html),
+                Segment::Slot(name) => {
+                    if let Some((_, component)) = self.slots.iter().find(|(slot, _)| slot == name) {
+                        component.render_to(cx);
+                    }
+                }
+            }
+        }
+    }
+
+    fn children(&self) -> Vec<&dyn Component> {
+        self.slots
+            .iter()
+            .map(|(_, component)| component as &dyn Component)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::v4::components::Strong;
+
+    #[test]
+    fn fills_slots_and_escapes_text() {
+        let source =
+            TemplateSource::parse("test", "<p>{{ name }} & {{who}}</p>", &["name", "who"]).unwrap();
+        let html = source
+            .template()
+            .slot("name", "<Ferris>")
+            .slot("who", Strong("friends"))
+            .render();
+
+        assert_eq!(html, "<p>&lt;Ferris&gt; & <strong>friends</strong></p>");
+    }
+
+    #[test]
+    fn build_rejects_missing_slots() {
+        let source =
+            TemplateSource::parse("test", "<p>{{ name }} & {{who}}</p>", &["name", "who"]).unwrap();
+        let err = source
+            .template()
+            .slot("name", "Ferris")
+            .build()
+            .err()
+            .unwrap();
+
+        assert_eq!(err.to_string(), "template `test` slot `who` was not filled");
+        assert!(source
+            .template()
+            .slot("name", "Ferris")
+            .slot("who", "friends")
+            .build()
+            .is_ok());
+    }
+
+    #[test]
+    #[should_panic(expected = "template `test` slot `who` was not filled")]
+    #[cfg(debug_assertions)]
+    fn render_panics_on_missing_slots_in_debug() {
+        let source =
+            TemplateSource::parse("test", "<p>{{ name }} & {{who}}</p>", &["name", "who"]).unwrap();
+        source.template().slot("name", "Ferris").render();
+    }
+
+    #[test]
+    fn rejects_unknown_slots() {
+        let err = TemplateSource::parse("test", "<p>\n{{ name }}</p>", &["title"])
+            .err()
+            .unwrap();
+
+        assert_eq!(
+            err.to_string(),
+            "template `test` references slot `name` on line 2, but only [title] are provided"
+        );
+    }
+}
@@ -0,0 +1,202 @@
+use std::collections::HashSet;
+
+use super::{
+    ast::{ExportError, Node},
+    cache::Event,
+    components::{Component, TrustedHtml},
+    context::RenderContext,
+    render::{HtmlRenderer, Renderer},
+};
+
+const PLACEHOLDER: &str = "<!--table-of-contents-->";
+
+#[derive(Clone)]
+pub(super) struct Entry {
+    level: u8,
+    id: String,
+    text: String,
+    in_main: bool,
+}
+
+#[derive(Default)]
+pub(super) struct Headings {
+    issued: HashSet<String>,
+    pub(super) entries: Vec<Entry>,
+    pub(super) collected: Option<Vec<Entry>>,
+    pub(super) main: usize,
+}
+
+impl Headings {
+    fn issue(&mut self, slug: &str) -> String {
+        let mut id = slug.to_string();
+        let mut suffix = 0;
+        while self.issued.contains(&id) {
+            suffix += 1;
+            id = format!("{slug}-{suffix}");
+        }
+        self.issued.insert(id.clone());
+        id
+    }
+}
+
+fn slug(text: &str) -> String {
+    let mut slug = String::new();
+
+    for c in text.chars().flat_map(char::to_lowercase) {
+        if c.is_alphanumeric() {
+            slug.push(c);
+        } else if (c.is_whitespace() || c == '-' || c == '_') && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+
+    match slug.trim_matches('-') {
+        "" => "section".to_string(),
+        slug => slug.to_string(),
+    }
+}
+
+pub(super) fn heading(cx: &mut RenderContext, level: u8, text: String) {
+    let id = cx.headings.issue(&slug(&text));
+    cx.renderer().attribute("id", &id);
+
+    cx.headings.entries.push(Entry {
+        level,
+        id,
+        text: text.clone(),
+        in_main: cx.headings.main > 0,
+    });
+    cx.record(Event::Heading(level, text));
+}
+
+fn contents(entries: &[Entry]) -> Vec<&Entry> {
+    if entries.iter().any(|entry| entry.in_main) {
+        entries.iter().filter(|entry| entry.in_main).collect()
+    } else {
+        entries.iter().collect()
+    }
+}
+
+fn list(entries: &[&Entry], cx: &mut RenderContext) {
+    cx.start_element("ul");
+
+    let mut index = 0;
+    while index < entries.len() {
+        let entry = entries[index];
+        let end = entries[index + 1..]
+            .iter()
+            .position(|next| next.level <= entry.level)
+            .map_or(entries.len(), |position| index + 1 + position);
+
+        cx.start_element("li");
+        cx.start_element("a");
+        cx.attribute("href", &format!("#{}", entry.id));
+        cx.text(&entry.text);
+        cx.end_element("a");
+        if end > index + 1 {
+            list(&entries[index + 1..end], cx);
+        }
+        cx.end_element("li");
+
+        index = end;
+    }
+
+    cx.end_element("ul");
+}
+
+fn table(entries: &[Entry], cx: &mut RenderContext) {
+    let entries = contents(entries);
+    if entries.is_empty() {
+        return;
+    }
+
+    cx.element("TableOfContents", "nav", |cx| {
+        cx.attribute("class", "toc");
+        list(&entries, cx);
+    });
+}
+
+pub(super) fn fill(html: String, entries: &[Entry]) -> String {
+    if !html.contains(PLACEHOLDER) {
+        return html;
+    }
+
+    let mut renderer = HtmlRenderer::default();
+    table(entries, &mut RenderContext::new(&mut renderer));
+    html.replace(PLACEHOLDER, &renderer.finish())
+}
+
+pub struct TableOfContents;
+
+impl Component for TableOfContents {
+    fn render_to(&self, cx: &mut RenderContext) {
+        match cx.headings.collected.clone() {
+            Some(collected) => table(&collected, cx),
+            None => cx.raw(&TrustedHtml::from_static(PLACEHOLDER)),
+        }
+    }
+
+    fn export(&self, nodes: &mut Vec<Node>) -> Result<(), ExportError> {
+        nodes.push(Node::TableOfContents);
+        Ok(())
+    }
+
+    fn tag(&self) -> Option<&'static str> {
+        Some("nav")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::v4::components::*;
+
+    #[test]
+    fn slugs_are_unique_per_page() {
+        let body = (
+            H2("Kansai Rust"),
+            Main((
+                TableOfContents,
+                H1("Rust talk: Tuples & <Iterators>"),
+                H2("Details"),
+                H3(("Agenda ", Em("(draft)"))),
+                H2("Details"),
+            )),
+        );
+
+        let mut renderer = HtmlRenderer::default();
+        let mut cx = RenderContext::new(&mut renderer);
+        body.render_to(&mut cx);
+        let entries = cx.headings.entries;
+        let html = fill(renderer.finish(), &entries);
+
+        assert_eq!(
+            html,
+            "<h2 id=\"kansai-rust\">Kansai Rust</h2><main>\
+            <nav class=\"toc\"><ul>\
+            <li><a href=\"#rust-talk-tuples-iterators\">Rust talk: Tuples &amp; &lt;Iterators&gt;</a><ul>\
+            <li><a href=\"#details\">Details</a><ul><li><a href=\"#agenda-draft\">Agenda (draft)</a></li></ul></li>\
+            <li><a href=\"#details-1\">Details</a></li>\
+            </ul></li></ul></nav>\
+            <h1 id=\"rust-talk-tuples-iterators\">Rust talk: Tuples &amp; &lt;Iterators&gt;</h1>\
+            <h2 id=\"details\">Details</h2>\
+            <h3 id=\"agenda-draft\">Agenda <em>(draft)</em></h3>\
+            <h2 id=\"details-1\">Details</h2></main>"
+        );
+    }
+
+    #[test]
+    fn suffixed_ids_do_not_collide_with_literal_headings() {
+        let mut renderer = HtmlRenderer::default();
+        let mut cx = RenderContext::new(&mut renderer);
+        (H2("Details"), H2("Details"), H2("Details 1"), H2("Details")).render_to(&mut cx);
+        let ids = cx
+            .headings
+            .entries
+            .iter()
+            .map(|entry| entry.id.as_str())
+            .collect::<Vec<_>>();
+
+        assert_eq!(ids, ["details", "details-1", "details-1-1", "details-2"]);
+    }
+}